@@ -0,0 +1,115 @@
+// emitted with emit_cpi! so they land in the inner instructions instead of the (truncatable) program logs
+// actor is the signer behind the transition, none for the permissionless cranks
+
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct QuestCreated {
+  pub quest_id: Pubkey,
+  pub actor: Option<Pubkey>,
+  pub new_status: u8,
+  pub staked: u64,
+  pub placement_paid: u64,
+  pub reward: u64,
+  pub slot: u64,
+}
+
+#[event]
+pub struct QuestStatusChanged {
+  pub quest_id: Pubkey,
+  pub actor: Option<Pubkey>,
+  pub old_status: u8,
+  pub new_status: u8,
+  pub slot: u64,
+}
+
+#[event]
+pub struct QuestAccepted {
+  pub quest_id: Pubkey,
+  pub actor: Option<Pubkey>,
+  pub offeree: Pubkey,
+  pub old_status: u8,
+  pub new_status: u8,
+  pub offeree_staked: u64,
+  pub slot: u64,
+}
+
+#[event]
+pub struct QuestAbandoned {
+  pub quest_id: Pubkey,
+  pub actor: Option<Pubkey>,
+  pub old_status: u8,
+  pub new_status: u8,
+  pub penalty: u64,
+  pub slot: u64,
+}
+
+#[event]
+pub struct MilestoneCompleted {
+  pub quest_id: Pubkey,
+  pub actor: Option<Pubkey>,
+  pub milestone: u8,
+  pub reward: u64,
+  pub slot: u64,
+}
+
+#[event]
+pub struct QuestCompleted {
+  pub quest_id: Pubkey,
+  pub actor: Option<Pubkey>,
+  pub old_status: u8,
+  pub new_status: u8,
+  pub offeree_staked: u64,
+  pub reward: u64,
+  pub slot: u64,
+}
+
+/// The Quest account is gone after this one, hence no new status.
+#[event]
+pub struct QuestClosed {
+  pub quest_id: Pubkey,
+  pub actor: Option<Pubkey>,
+  pub old_status: u8,
+  pub owner_amount: u64,
+  pub offeree_amount: u64,
+  pub decay_fee: u64,
+  pub slot: u64,
+}
+
+#[event]
+pub struct DeliverableSubmitted {
+  pub quest_id: Pubkey,
+  pub actor: Option<Pubkey>,
+  pub deliverable_hash: [u8; 32],
+  /// The slot the owner's review period ends, after which the offeree can claim completion.
+  pub review_ends: u64,
+  pub slot: u64,
+}
+
+#[event]
+pub struct VoteCast {
+  pub quest_id: Pubkey,
+  pub actor: Option<Pubkey>,
+  /// 0: owner, 1: offeree, 2: abstain.
+  pub vote: u8,
+  pub power: u64,
+  pub slot: u64,
+}
+
+/// The application events carry the address of the Quest account rather than its id,
+/// an application can be withdrawn after the Quest is closed.
+#[event]
+pub struct ApplicationSubmitted {
+  pub quest: Pubkey,
+  pub actor: Option<Pubkey>,
+  pub staked: u64,
+  pub slot: u64,
+}
+
+#[event]
+pub struct ApplicationWithdrawn {
+  pub quest: Pubkey,
+  pub actor: Option<Pubkey>,
+  pub amount: u64,
+  pub slot: u64,
+}
@@ -0,0 +1,192 @@
+// only the offeree signs this
+// abandoning a taken quest - the owner (or the treasury, see config.abandon_penalty_to_treasury) gets the abandon
+// penalty out of the offeree's stake, the offeree the rest, and the quest is open again for another offeree
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
+
+use crate::{
+  events::QuestAbandoned,
+  state::Quest,
+  state::QuestStatus,
+  state::QuestError,
+  state::Config,
+  state::ConfigError,
+  state::Counter,
+  state::MAX_ABANDON_PENALTY,
+  state::UserProfile,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AbandonQuest<'info> {
+  #[account(
+    mut,
+    seeds = [
+      b"quest",
+      quest.id.key().as_ref(),
+    ],
+    bump = quest.bump,
+    has_one = owner,
+    constraint = quest.is_migrated() @ QuestError::QuestNotMigrated,
+    constraint = quest.is(QuestStatus::Taken) @ QuestError::AbandonNotAllowed,
+    constraint = quest.offeree == Some(offeree.key()) @ QuestError::NotQuestOfferee,
+  )]
+  pub quest: Box<Account<'info, Quest>>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = quest,
+  )]
+  pub escrow_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = owner,
+  )]
+  pub owner_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = treasury,
+  )]
+  pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = offeree,
+  )]
+  pub offeree_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    seeds = [
+      b"config",
+    ],
+    bump = config.bump,
+    has_one = token_mint,
+    has_one = treasury,
+    constraint = !config.exits_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Account<'info, Config>,
+
+  #[account(
+    mut,
+    seeds = [
+      b"counter",
+    ],
+    bump = counter.bump,
+  )]
+  pub counter: Account<'info, Counter>,
+
+  #[account(
+    init_if_needed,
+    payer = offeree,
+    seeds = [
+      b"profile",
+      offeree.key().as_ref(),
+    ],
+    bump,
+    space = UserProfile::len(),
+  )]
+  pub offeree_profile: Box<Account<'info, UserProfile>>,
+
+  /// CHECK: has_one in the config account
+  pub treasury: UncheckedAccount<'info>,
+
+  /// CHECK: has_one in the quest account
+  pub owner: UncheckedAccount<'info>,
+
+  #[account(mut)]
+  pub offeree: Signer<'info>,
+
+  pub token_mint: Account<'info, Mint>,
+
+  pub system_program: Program<'info, System>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_program: Program<'info, Token>,
+  pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn abandon_quest_handler(ctx: Context<AbandonQuest>) -> Result<()> {
+
+  let quest = &mut ctx.accounts.quest;
+  let id = quest.id.key();
+  let quest_bump = quest.bump.to_le_bytes();
+  let counter = &mut ctx.accounts.counter;
+
+  let offeree_staked = quest.offeree_staked.unwrap();
+  let penalty = (offeree_staked as u128 * ctx.accounts.config.abandon_penalty as u128 / MAX_ABANDON_PENALTY as u128) as u64;
+
+  let seeds = vec![
+    b"quest".as_ref(),
+    id.as_ref(),
+    quest_bump.as_ref()
+  ];
+  let signer = vec![seeds.as_slice()];
+
+  if penalty > 0 {
+    let penalty_token_account = if ctx.accounts.config.abandon_penalty_to_treasury {
+      ctx.accounts.treasury_token_account.as_ref().ok_or(QuestError::TreasuryTokenAccountMissing)?.to_account_info()
+    } else {
+      ctx.accounts.owner_token_account.to_account_info()
+    };
+
+    let transfer_ix = Transfer {
+      from: ctx.accounts.escrow_token_account.to_account_info(),
+      to: penalty_token_account,
+      authority: quest.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+      ctx.accounts.token_program.to_account_info(),
+      transfer_ix,
+      signer.as_slice()
+    );
+    transfer(cpi_ctx, penalty)?;
+  }
+
+  if offeree_staked > penalty {
+    let transfer_ix = Transfer {
+      from: ctx.accounts.escrow_token_account.to_account_info(),
+      to: ctx.accounts.offeree_token_account.to_account_info(),
+      authority: quest.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+      ctx.accounts.token_program.to_account_info(),
+      transfer_ix,
+      signer.as_slice()
+    );
+    transfer(cpi_ctx, offeree_staked - penalty)?;
+  }
+
+  quest.offeree = None;
+  quest.offeree_staked = None;
+  quest.offeree_proposal_hash = None;
+  quest.accepted_timestamp = None;
+  quest.deliverable_hash = None;
+  quest.deliverable_timestamp = None;
+
+  let offeree_profile = &mut ctx.accounts.offeree_profile;
+  offeree_profile.bump = ctx.bumps.offeree_profile;
+  offeree_profile.wallet = ctx.accounts.offeree.key();
+  offeree_profile.quests_abandoned += 1;
+
+  quest.transition(QuestStatus::Open)?;
+  counter.posts_taken -= 1;
+  counter.posts_open += 1;
+
+  emit_cpi!(QuestAbandoned {
+    quest_id: quest.id,
+    actor: Some(ctx.accounts.offeree.key()),
+    old_status: QuestStatus::Taken as u8,
+    new_status: quest.status,
+    penalty,
+    slot: Clock::get()?.slot,
+  });
+
+  Ok(())
+}
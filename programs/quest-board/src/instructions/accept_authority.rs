@@ -0,0 +1,31 @@
+// second step of the authority handover, signed by the proposed authority
+
+use anchor_lang::prelude::*;
+use crate::{state::Config, state::ConfigError};
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+
+  #[account(
+    mut,
+    seeds = [
+      b"config",
+    ],
+    bump = config.bump,
+    constraint = config.pending_authority == Some(pending_authority.key()) @ ConfigError::InvalidPendingAuthority,
+  )]
+  pub config: Box<Account<'info, Config>>,
+
+  pub pending_authority: Signer<'info>,
+
+  pub system_program: Program<'info, System>,
+}
+
+pub fn accept_authority_handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+  let config = &mut ctx.accounts.config;
+
+  config.authority = ctx.accounts.pending_authority.key();
+  config.pending_authority = None;
+
+  Ok(())
+}
@@ -6,10 +6,15 @@ use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
 
 use crate::{
+  events::QuestAccepted,
   state::Quest,
+  state::QuestStatus,
   state::QuestError,
   state::Config,
+  state::ConfigError,
   state::Counter,
+  state::UserProfile,
+  state::UserProfileError,
 };
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
@@ -18,6 +23,7 @@ pub struct AcceptQuestParams {
   pub offeree_proposal_hash: [u8; 32],
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: AcceptQuestParams)]
 pub struct AcceptQuest<'info> {
@@ -30,7 +36,10 @@ pub struct AcceptQuest<'info> {
     ],
     bump = quest.bump,
     has_one = owner,
-    constraint = quest.status == 1 @ QuestError::QuestNotOpen,
+    realloc = Quest::len(),
+    realloc::payer = owner,
+    realloc::zero = false,
+    constraint = quest.is(QuestStatus::Open) @ QuestError::QuestNotOpen,
     constraint = offeree.key() != owner.key() @ QuestError::OwnerCannotAcceptOwnQuest,
   )]
   pub quest: Box<Account<'info, Quest>>,
@@ -52,16 +61,32 @@ pub struct AcceptQuest<'info> {
   )]
   pub offeree_token_account: Account<'info, TokenAccount>,
 
+  #[account(
+    init_if_needed,
+    payer = offeree,
+    seeds = [
+      b"profile",
+      offeree.key().as_ref(),
+    ],
+    bump,
+    space = UserProfile::len(),
+    constraint = offeree_profile.meets_reputation(quest.min_reputation) @ UserProfileError::ReputationTooLow,
+  )]
+  pub offeree_profile: Box<Account<'info, UserProfile>>,
+
   pub token_mint: Account<'info, Mint>,
 
+  #[account(mut)]
   pub offeree: Signer<'info>,
 
+  #[account(mut)]
   pub owner: Signer<'info>,
 
   #[account(
     seeds = [b"config"],
     bump = config.bump,
     has_one = token_mint,
+    constraint = !config.is_paused() @ ConfigError::ProgramPaused,
   )]
   pub config: Account<'info, Config>,
 
@@ -82,6 +107,11 @@ pub fn accept_quest_handler(ctx: Context<AcceptQuest>, params: AcceptQuestParams
 
   let quest = &mut ctx.accounts.quest;
   let counter = &mut ctx.accounts.counter;
+  let slot = Clock::get()?.slot;
+
+  if quest.accept_deadline.is_some_and(|deadline| slot > deadline) {
+    return Err(QuestError::QuestExpired.into());
+  }
 
   // transfer governance tokens
   let cpi_accounts = Transfer {
@@ -96,11 +126,27 @@ pub fn accept_quest_handler(ctx: Context<AcceptQuest>, params: AcceptQuestParams
   quest.offeree = Some(ctx.accounts.offeree.key());
   quest.offeree_staked = Some(params.stake_amount);
   quest.offeree_proposal_hash = Some(params.offeree_proposal_hash);
-  quest.accepted_timestamp = Some(Clock::get()?.slot);
+  quest.accepted_timestamp = Some(slot);
+  quest.offer_nonce += 1;
   
-  quest.status = 3;
+  let offeree_profile = &mut ctx.accounts.offeree_profile;
+  offeree_profile.bump = ctx.bumps.offeree_profile;
+  offeree_profile.wallet = ctx.accounts.offeree.key();
+  offeree_profile.quests_taken += 1;
+
+  quest.transition(QuestStatus::Taken)?;
   counter.posts_open -= 1;
   counter.posts_taken += 1;
 
+  emit_cpi!(QuestAccepted {
+    quest_id: quest.id,
+    actor: Some(ctx.accounts.offeree.key()),
+    offeree: ctx.accounts.offeree.key(),
+    old_status: QuestStatus::Open as u8,
+    new_status: quest.status,
+    offeree_staked: params.stake_amount,
+    slot,
+  });
+
   Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,219 @@
+// only the offeree signs this, the owner's consent comes from an offer signed off-chain
+// the instruction right before this one must be an Ed25519 program instruction verifying the offer:
+//   "quest_board:offer" | quest id (32) | offeree (32) | offer nonce (u64 le) | min stake (u64 le)
+//   | max stake (u64 le) | proposal hash (32) | expiry slot (u64 le)
+// the offer nonce of the quest is bumped on every acceptance, so a signed offer cannot be replayed
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar::instructions::{self, get_instruction_relative};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
+
+use crate::{
+  events::QuestAccepted,
+  instructions::AcceptQuestParams,
+  state::Quest,
+  state::QuestStatus,
+  state::QuestError,
+  state::Config,
+  state::ConfigError,
+  state::Counter,
+  state::UserProfile,
+  state::UserProfileError,
+};
+
+pub const OFFER_MESSAGE_PREFIX: &[u8] = b"quest_board:offer";
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct AcceptSignedOfferParams {
+  pub accept: AcceptQuestParams,
+  pub min_stake: u64,
+  pub max_stake: u64,
+  pub expiry_slot: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: AcceptSignedOfferParams)]
+pub struct AcceptSignedOffer<'info> {
+
+  #[account(
+    mut,
+    seeds = [
+      b"quest",
+      quest.id.key().as_ref(),
+    ],
+    bump = quest.bump,
+    constraint = quest.is_migrated() @ QuestError::QuestNotMigrated,
+    constraint = quest.is(QuestStatus::Open) @ QuestError::QuestNotOpen,
+    constraint = offeree.key() != quest.owner @ QuestError::OwnerCannotAcceptOwnQuest,
+  )]
+  pub quest: Box<Account<'info, Quest>>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = quest,
+  )]
+  pub escrow_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = offeree,
+    constraint = offeree_token_account.amount >= params.accept.stake_amount @ QuestError::NotEnoughTokenBalance,
+    constraint = params.accept.stake_amount >= quest.min_stake_required.max(params.min_stake) @ QuestError::StakeAmountTooLow,
+    constraint = params.accept.stake_amount <= quest.staked.min(params.max_stake) @ QuestError::StakeAmountTooHigh,
+  )]
+  pub offeree_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    init_if_needed,
+    payer = offeree,
+    seeds = [
+      b"profile",
+      offeree.key().as_ref(),
+    ],
+    bump,
+    space = UserProfile::len(),
+    constraint = offeree_profile.meets_reputation(quest.min_reputation) @ UserProfileError::ReputationTooLow,
+  )]
+  pub offeree_profile: Box<Account<'info, UserProfile>>,
+
+  pub token_mint: Account<'info, Mint>,
+
+  #[account(mut)]
+  pub offeree: Signer<'info>,
+
+  #[account(
+    seeds = [b"config"],
+    bump = config.bump,
+    has_one = token_mint,
+    constraint = !config.is_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Account<'info, Config>,
+
+  #[account(
+    mut,
+    seeds = [b"counter"],
+    bump = counter.bump,
+  )]
+  pub counter: Account<'info, Counter>,
+
+  #[account(address = instructions::ID)]
+  /// CHECK: the instructions sysvar
+  pub instructions: UncheckedAccount<'info>,
+
+  pub system_program: Program<'info, System>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_program: Program<'info, Token>,
+  pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn offer_message(quest_id: &Pubkey, offeree: &Pubkey, offer_nonce: u64, params: &AcceptSignedOfferParams) -> Vec<u8> {
+  [
+    OFFER_MESSAGE_PREFIX,
+    quest_id.as_ref(),
+    offeree.as_ref(),
+    &offer_nonce.to_le_bytes(),
+    &params.min_stake.to_le_bytes(),
+    &params.max_stake.to_le_bytes(),
+    &params.accept.offeree_proposal_hash,
+    &params.expiry_slot.to_le_bytes(),
+  ].concat()
+}
+
+/// Checks that the instruction is an Ed25519 program instruction with a single signature of
+/// `message` by `signer`, with everything contained in the instruction itself.
+fn verify_ed25519_ix(ix: &Instruction, signer: &Pubkey, message: &[u8]) -> Result<()> {
+  let data = &ix.data;
+
+  // num_signatures (1), padding (1), then 7 u16 offsets
+  if ix.program_id != ed25519_program::ID || data.len() < 16 || data[0] != 1 {
+    return Err(QuestError::InvalidOfferSignature.into());
+  }
+
+  let offset = |i: usize| u16::from_le_bytes([data[2 + i * 2], data[3 + i * 2]]);
+  let signature_instruction_index = offset(1);
+  let public_key_offset = offset(2) as usize;
+  let public_key_instruction_index = offset(3);
+  let message_data_offset = offset(4) as usize;
+  let message_data_size = offset(5) as usize;
+  let message_instruction_index = offset(6);
+
+  // the signature, public key and message should not be read from other instructions
+  if signature_instruction_index != u16::MAX ||
+    public_key_instruction_index != u16::MAX ||
+    message_instruction_index != u16::MAX {
+    return Err(QuestError::InvalidOfferSignature.into());
+  }
+
+  let public_key = data.get(public_key_offset..public_key_offset + 32);
+  let signed_message = data.get(message_data_offset..message_data_offset + message_data_size);
+
+  if public_key != Some(signer.as_ref()) || signed_message != Some(message) {
+    return Err(QuestError::InvalidOfferSignature.into());
+  }
+
+  Ok(())
+}
+
+pub fn accept_signed_offer_handler(ctx: Context<AcceptSignedOffer>, params: AcceptSignedOfferParams) -> Result<()> {
+
+  let quest = &mut ctx.accounts.quest;
+  let counter = &mut ctx.accounts.counter;
+  let offeree = ctx.accounts.offeree.key();
+  let slot = Clock::get()?.slot;
+
+  if slot > params.expiry_slot {
+    return Err(QuestError::OfferExpired.into());
+  }
+
+  if quest.accept_deadline.is_some_and(|deadline| slot > deadline) {
+    return Err(QuestError::QuestExpired.into());
+  }
+
+  // the Ed25519 program already verified the signature, check what was signed and by whom
+  let ed25519_ix = get_instruction_relative(-1, &ctx.accounts.instructions.to_account_info())
+    .map_err(|_| QuestError::InvalidOfferSignature)?;
+  verify_ed25519_ix(&ed25519_ix, &quest.owner, &offer_message(&quest.id, &offeree, quest.offer_nonce, &params))?;
+
+  // transfer governance tokens
+  let cpi_accounts = Transfer {
+    from: ctx.accounts.offeree_token_account.to_account_info(),
+    to: ctx.accounts.escrow_token_account.to_account_info(),
+    authority: ctx.accounts.offeree.to_account_info(),
+  };
+  let cpi_program = ctx.accounts.token_program.to_account_info();
+  let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+  transfer(cpi_ctx, params.accept.stake_amount)?;
+
+  quest.offeree = Some(offeree);
+  quest.offeree_staked = Some(params.accept.stake_amount);
+  quest.offeree_proposal_hash = Some(params.accept.offeree_proposal_hash);
+  quest.accepted_timestamp = Some(slot);
+  quest.offer_nonce += 1;
+
+  let offeree_profile = &mut ctx.accounts.offeree_profile;
+  offeree_profile.bump = ctx.bumps.offeree_profile;
+  offeree_profile.wallet = ctx.accounts.offeree.key();
+  offeree_profile.quests_taken += 1;
+
+  quest.transition(QuestStatus::Taken)?;
+  counter.posts_open -= 1;
+  counter.posts_taken += 1;
+
+  emit_cpi!(QuestAccepted {
+    quest_id: quest.id,
+    actor: Some(offeree),
+    offeree,
+    old_status: QuestStatus::Open as u8,
+    new_status: quest.status,
+    offeree_staked: params.accept.stake_amount,
+    slot,
+  });
+
+  Ok(())
+}
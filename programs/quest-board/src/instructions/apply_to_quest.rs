@@ -0,0 +1,144 @@
+// only the applicant signs this, the stake is escrowed in the application until approved or withdrawn
+// the owner picks one of the applications in approve_application
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
+
+use crate::{
+  events::ApplicationSubmitted,
+  state::Application,
+  state::Quest,
+  state::QuestStatus,
+  state::QuestError,
+  state::Config,
+  state::ConfigError,
+  state::UserProfile,
+  state::UserProfileError,
+};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct ApplyToQuestParams {
+  pub stake_amount: u64,
+  pub offeree_proposal_hash: [u8; 32],
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: ApplyToQuestParams)]
+pub struct ApplyToQuest<'info> {
+
+  #[account(
+    seeds = [
+      b"quest",
+      quest.id.key().as_ref(),
+    ],
+    bump = quest.bump,
+    constraint = quest.is(QuestStatus::Open) @ QuestError::QuestNotOpen,
+    constraint = offeree.key() != quest.owner @ QuestError::OwnerCannotAcceptOwnQuest,
+  )]
+  pub quest: Box<Account<'info, Quest>>,
+
+  #[account(
+    init,
+    payer = offeree,
+    seeds = [
+      b"application",
+      quest.key().as_ref(),
+      offeree.key().as_ref(),
+    ],
+    bump,
+    space = Application::len()
+  )]
+  pub application: Box<Account<'info, Application>>,
+
+  #[account(
+    init_if_needed,
+    payer = offeree,
+    associated_token::mint = token_mint,
+    associated_token::authority = application,
+  )]
+  pub application_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = offeree,
+    constraint = offeree_token_account.amount >= params.stake_amount @ QuestError::NotEnoughTokenBalance,
+    constraint = params.stake_amount >= quest.min_stake_required @ QuestError::StakeAmountTooLow,
+    constraint = params.stake_amount <= quest.staked @ QuestError::StakeAmountTooHigh,
+  )]
+  pub offeree_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    init_if_needed,
+    payer = offeree,
+    seeds = [
+      b"profile",
+      offeree.key().as_ref(),
+    ],
+    bump,
+    space = UserProfile::len(),
+    constraint = offeree_profile.meets_reputation(quest.min_reputation) @ UserProfileError::ReputationTooLow,
+  )]
+  pub offeree_profile: Box<Account<'info, UserProfile>>,
+
+  pub token_mint: Account<'info, Mint>,
+
+  #[account(
+    seeds = [b"config"],
+    bump = config.bump,
+    has_one = token_mint,
+    constraint = !config.is_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Account<'info, Config>,
+
+  #[account(mut)]
+  pub offeree: Signer<'info>,
+
+  pub system_program: Program<'info, System>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_program: Program<'info, Token>,
+  pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn apply_to_quest_handler(ctx: Context<ApplyToQuest>, params: ApplyToQuestParams) -> Result<()> {
+
+  let quest = &ctx.accounts.quest;
+  let application = &mut ctx.accounts.application;
+  let slot = Clock::get()?.slot;
+
+  if quest.accept_deadline.is_some_and(|deadline| slot > deadline) {
+    return Err(QuestError::QuestExpired.into());
+  }
+
+  // transfer governance tokens
+  let cpi_accounts = Transfer {
+    from: ctx.accounts.offeree_token_account.to_account_info(),
+    to: ctx.accounts.application_token_account.to_account_info(),
+    authority: ctx.accounts.offeree.to_account_info(),
+  };
+  let cpi_program = ctx.accounts.token_program.to_account_info();
+  let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+  transfer(cpi_ctx, params.stake_amount)?;
+
+  let offeree_profile = &mut ctx.accounts.offeree_profile;
+  offeree_profile.bump = ctx.bumps.offeree_profile;
+  offeree_profile.wallet = ctx.accounts.offeree.key();
+
+  application.bump = ctx.bumps.application;
+  application.quest = quest.key();
+  application.offeree = ctx.accounts.offeree.key();
+  application.staked = params.stake_amount;
+  application.proposal_hash = params.offeree_proposal_hash;
+  application.timestamp = slot;
+
+  emit_cpi!(ApplicationSubmitted {
+    quest: application.quest,
+    actor: Some(application.offeree),
+    staked: application.staked,
+    slot,
+  });
+
+  Ok(())
+}
@@ -0,0 +1,211 @@
+// only the owner signs this, the approved applicant becomes the offeree
+// the application's stake moves to the quest escrow, and the application is closed
+// anything else sent to the application's token account goes back to the applicant
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer, CloseAccount};
+
+use crate::{
+  events::QuestAccepted,
+  state::Application,
+  state::Quest,
+  state::QuestStatus,
+  state::QuestError,
+  state::Config,
+  state::ConfigError,
+  state::Counter,
+  state::UserProfile,
+  state::UserProfileError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ApproveApplication<'info> {
+
+  #[account(
+    mut,
+    seeds = [
+      b"quest",
+      quest.id.key().as_ref(),
+    ],
+    bump = quest.bump,
+    has_one = owner,
+    realloc = Quest::len(),
+    realloc::payer = owner,
+    realloc::zero = false,
+    constraint = quest.is(QuestStatus::Open) @ QuestError::QuestNotOpen,
+  )]
+  pub quest: Box<Account<'info, Quest>>,
+
+  #[account(
+    mut,
+    seeds = [
+      b"application",
+      quest.key().as_ref(),
+      offeree.key().as_ref(),
+    ],
+    bump = application.bump,
+    has_one = quest,
+    has_one = offeree,
+    close = offeree,
+    constraint = application.staked >= quest.min_stake_required @ QuestError::StakeAmountTooLow,
+    constraint = application.staked <= quest.staked @ QuestError::StakeAmountTooHigh,
+  )]
+  pub application: Box<Account<'info, Application>>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = application,
+  )]
+  pub application_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = quest,
+  )]
+  pub escrow_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = offeree,
+  )]
+  pub offeree_token_account: Account<'info, TokenAccount>,
+
+  pub token_mint: Account<'info, Mint>,
+
+  #[account(mut)]
+  /// CHECK: has_one in the application account
+  pub offeree: UncheckedAccount<'info>,
+
+  #[account(
+    init_if_needed,
+    payer = owner,
+    seeds = [
+      b"profile",
+      offeree.key().as_ref(),
+    ],
+    bump,
+    space = UserProfile::len(),
+    constraint = offeree_profile.meets_reputation(quest.min_reputation) @ UserProfileError::ReputationTooLow,
+  )]
+  pub offeree_profile: Box<Account<'info, UserProfile>>,
+
+  #[account(mut)]
+  pub owner: Signer<'info>,
+
+  #[account(
+    seeds = [b"config"],
+    bump = config.bump,
+    has_one = token_mint,
+    constraint = !config.is_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Account<'info, Config>,
+
+  #[account(
+    mut,
+    seeds = [b"counter"],
+    bump = counter.bump,
+  )]
+  pub counter: Account<'info, Counter>,
+
+  pub system_program: Program<'info, System>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_program: Program<'info, Token>,
+  pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn approve_application_handler(ctx: Context<ApproveApplication>) -> Result<()> {
+
+  let quest = &mut ctx.accounts.quest;
+  let application = &ctx.accounts.application;
+  let counter = &mut ctx.accounts.counter;
+  let quest_key = quest.key();
+  let offeree = ctx.accounts.offeree.key();
+  let application_bump = application.bump.to_le_bytes();
+  let slot = Clock::get()?.slot;
+
+  if quest.accept_deadline.is_some_and(|deadline| slot > deadline) {
+    return Err(QuestError::QuestExpired.into());
+  }
+
+  let seeds = vec![
+    b"application".as_ref(),
+    quest_key.as_ref(),
+    offeree.as_ref(),
+    application_bump.as_ref()
+  ];
+  let signer = vec![seeds.as_slice()];
+
+  // move the application's stake into the quest escrow
+  let transfer_ix = Transfer {
+    from: ctx.accounts.application_token_account.to_account_info(),
+    to: ctx.accounts.escrow_token_account.to_account_info(),
+    authority: application.to_account_info(),
+  };
+  let cpi_ctx = CpiContext::new_with_signer(
+    ctx.accounts.token_program.to_account_info(),
+    transfer_ix,
+    signer.as_slice()
+  );
+  transfer(cpi_ctx, application.staked)?;
+
+  // the token account must be empty to be closed
+  let excess = ctx.accounts.application_token_account.amount - application.staked;
+
+  if excess > 0 {
+    let transfer_ix = Transfer {
+      from: ctx.accounts.application_token_account.to_account_info(),
+      to: ctx.accounts.offeree_token_account.to_account_info(),
+      authority: application.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+      ctx.accounts.token_program.to_account_info(),
+      transfer_ix,
+      signer.as_slice()
+    );
+    transfer(cpi_ctx, excess)?;
+  }
+
+  let close_ix = CloseAccount {
+    account: ctx.accounts.application_token_account.to_account_info(),
+    destination: ctx.accounts.offeree.to_account_info(),
+    authority: application.to_account_info(),
+  };
+  let cpi_ctx = CpiContext::new_with_signer(
+    ctx.accounts.token_program.to_account_info(),
+    close_ix,
+    signer.as_slice(),
+  );
+  anchor_spl::token::close_account(cpi_ctx)?;
+
+  quest.offeree = Some(offeree);
+  quest.offeree_staked = Some(application.staked);
+  quest.offeree_proposal_hash = Some(application.proposal_hash);
+  quest.accepted_timestamp = Some(slot);
+  quest.offer_nonce += 1;
+
+  let offeree_profile = &mut ctx.accounts.offeree_profile;
+  offeree_profile.bump = ctx.bumps.offeree_profile;
+  offeree_profile.wallet = offeree;
+  offeree_profile.quests_taken += 1;
+
+  quest.transition(QuestStatus::Taken)?;
+  counter.posts_open -= 1;
+  counter.posts_taken += 1;
+
+  emit_cpi!(QuestAccepted {
+    quest_id: quest.id,
+    actor: Some(ctx.accounts.owner.key()),
+    offeree,
+    old_status: QuestStatus::Open as u8,
+    new_status: quest.status,
+    offeree_staked: application.staked,
+    slot,
+  });
+
+  Ok(())
+}
@@ -0,0 +1,129 @@
+// both parties must sign this
+
+// cancelling a taken quest - the offeree gets back their staked governance token,
+// and the quest goes back to unpublished so the owner can publish or close it
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
+
+use crate::{
+  events::QuestStatusChanged,
+  state::Quest,
+  state::QuestStatus,
+  state::QuestError,
+  state::Config,
+  state::ConfigError,
+  state::Counter,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelQuest<'info> {
+  #[account(
+    mut,
+    seeds = [
+      b"quest",
+      quest.id.key().as_ref(),
+    ],
+    bump = quest.bump,
+    has_one = owner,
+    realloc = Quest::len(),
+    realloc::payer = owner,
+    realloc::zero = false,
+    constraint = quest.is(QuestStatus::Taken) @ QuestError::CancelNotAllowed,
+    constraint = quest.offeree == Some(offeree.key()) @ QuestError::NotQuestOfferee,
+  )]
+  pub quest: Box<Account<'info, Quest>>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = quest,
+  )]
+  pub escrow_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = offeree,
+  )]
+  pub offeree_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    seeds = [
+      b"config",
+    ],
+    bump = config.bump,
+    has_one = token_mint,
+    constraint = !config.exits_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Account<'info, Config>,
+
+  #[account(
+    mut,
+    seeds = [
+      b"counter",
+    ],
+    bump = counter.bump,
+  )]
+  pub counter: Account<'info, Counter>,
+
+  #[account(mut)]
+  pub owner: Signer<'info>,
+
+  pub offeree: Signer<'info>,
+
+  pub token_mint: Account<'info, Mint>,
+
+  pub system_program: Program<'info, System>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_program: Program<'info, Token>,
+  pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn cancel_quest_handler(ctx: Context<CancelQuest>) -> Result<()> {
+
+  let quest = &mut ctx.accounts.quest;
+  let id = quest.id.key();
+  let quest_bump = quest.bump.to_le_bytes();
+  let counter = &mut ctx.accounts.counter;
+
+  let offeree_staked = quest.offeree_staked.unwrap();
+
+  let seeds = vec![
+    b"quest".as_ref(),
+    id.as_ref(),
+    quest_bump.as_ref()
+  ];
+  let signer = vec![seeds.as_slice()];
+
+  let transfer_ix = Transfer {
+    from: ctx.accounts.escrow_token_account.to_account_info(),
+    to: ctx.accounts.offeree_token_account.to_account_info(),
+    authority: quest.to_account_info(),
+  };
+  let cpi_program = ctx.accounts.token_program.to_account_info();
+  let cpi_ctx = CpiContext::new_with_signer(cpi_program, transfer_ix, signer.as_slice());
+  transfer(cpi_ctx, offeree_staked)?;
+
+  quest.offeree = None;
+  quest.offeree_staked = None;
+  quest.offeree_proposal_hash = None;
+  quest.accepted_timestamp = None;
+  quest.deliverable_hash = None;
+  quest.deliverable_timestamp = None;
+
+  quest.transition(QuestStatus::Unpublished)?;
+  counter.posts_taken -= 1;
+
+  emit_cpi!(QuestStatusChanged {
+    quest_id: quest.id,
+    actor: Some(ctx.accounts.owner.key()),
+    old_status: QuestStatus::Taken as u8,
+    new_status: quest.status,
+    slot: Clock::get()?.slot,
+  });
+
+  Ok(())
+}
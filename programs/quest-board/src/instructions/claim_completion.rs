@@ -0,0 +1,213 @@
+// only the offeree signs this, after the owner's review period of the deliverable has passed
+// performs the same transfers as complete_quest, through the shared Completion
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Mint};
+use anchor_spl::token_2022::Token2022;
+
+use crate::{
+  events::QuestCompleted,
+  instructions::Completion,
+  state::Quest,
+  state::QuestStatus,
+  state::QuestError,
+  state::Config,
+  state::ConfigError,
+  state::Counter,
+  state::UserProfile,
+  state::Badge,
+  state::BadgeMint,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimCompletion<'info> {
+  #[account(
+    mut,
+    seeds = [
+      b"quest",
+      quest.id.key().as_ref(),
+    ],
+    bump = quest.bump,
+    has_one = owner,
+    constraint = quest.is_migrated() @ QuestError::QuestNotMigrated,
+    constraint = quest.is(QuestStatus::Taken) @ QuestError::CompleteNotAllowed,
+    constraint = quest.offeree == Some(offeree.key()) @ QuestError::NotQuestOfferee,
+    constraint = quest.deliverable_timestamp.is_some() @ QuestError::NoDeliverable,
+  )]
+  pub quest: Box<Account<'info, Quest>>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = quest,
+  )]
+  pub escrow_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = offeree,
+  )]
+  pub offeree_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    constraint = quest.reward_mint == Some(reward_mint.key()) @ QuestError::InvalidRewardMint,
+  )]
+  pub reward_mint: Option<Box<Account<'info, Mint>>>,
+
+  #[account(
+    mut,
+    associated_token::mint = reward_mint,
+    associated_token::authority = quest,
+  )]
+  pub escrow_reward_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+  #[account(
+    mut,
+    associated_token::mint = reward_mint,
+    associated_token::authority = offeree,
+  )]
+  pub offeree_reward_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+  #[account(
+    seeds = [
+      b"config",
+    ],
+    bump = config.bump,
+    has_one = token_mint,
+    constraint = !config.is_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Account<'info, Config>,
+
+  #[account(
+    mut,
+    seeds = [
+      b"counter",
+    ],
+    bump = counter.bump,
+  )]
+  pub counter: Account<'info, Counter>,
+
+  #[account(
+    init_if_needed,
+    payer = offeree,
+    seeds = [
+      b"profile",
+      owner.key().as_ref(),
+    ],
+    bump,
+    space = UserProfile::len(),
+  )]
+  pub owner_profile: Box<Account<'info, UserProfile>>,
+
+  #[account(
+    init_if_needed,
+    payer = offeree,
+    seeds = [
+      b"profile",
+      offeree.key().as_ref(),
+    ],
+    bump,
+    space = UserProfile::len(),
+  )]
+  pub offeree_profile: Box<Account<'info, UserProfile>>,
+
+  /// CHECK: has_one in the quest account
+  pub owner: UncheckedAccount<'info>,
+
+  #[account(mut)]
+  pub offeree: Signer<'info>,
+
+  pub token_mint: Account<'info, Mint>,
+
+  // the completion badge is minted only if these accounts are passed
+
+  #[account(
+    init,
+    payer = offeree,
+    seeds = [
+      b"badge",
+      quest.key().as_ref(),
+    ],
+    bump,
+    space = Badge::len()
+  )]
+  pub badge: Option<Box<Account<'info, Badge>>>,
+
+  #[account(
+    mut,
+    seeds = [
+      b"badge_mint",
+      quest.key().as_ref(),
+    ],
+    bump,
+  )]
+  /// CHECK: created as a non-transferable Token-2022 mint when minting the badge
+  pub badge_mint: Option<UncheckedAccount<'info>>,
+
+  #[account(mut)]
+  /// CHECK: the offeree's associated token account of the badge mint, created by the associated token program
+  pub offeree_badge_token_account: Option<UncheckedAccount<'info>>,
+
+  pub token_2022_program: Option<Program<'info, Token2022>>,
+
+  pub system_program: Program<'info, System>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_program: Program<'info, Token>,
+  pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn claim_completion_handler(ctx: Context<ClaimCompletion>) -> Result<()> {
+
+  let deliverable_timestamp = ctx.accounts.quest.deliverable_timestamp.unwrap();
+
+  if Clock::get()?.slot <= deliverable_timestamp.saturating_add(ctx.accounts.config.deliverable_review_period) {
+    return Err(QuestError::ReviewPeriodActive.into());
+  }
+
+  let owner_profile = &mut ctx.accounts.owner_profile;
+  owner_profile.bump = ctx.bumps.owner_profile;
+  owner_profile.wallet = ctx.accounts.owner.key();
+
+  let offeree_profile = &mut ctx.accounts.offeree_profile;
+  offeree_profile.bump = ctx.bumps.offeree_profile;
+  offeree_profile.wallet = ctx.accounts.offeree.key();
+
+  let (offeree_staked, reward) = Completion {
+    quest: &mut ctx.accounts.quest,
+    escrow_token_account: ctx.accounts.escrow_token_account.to_account_info(),
+    offeree_token_account: ctx.accounts.offeree_token_account.to_account_info(),
+    escrow_reward_token_account: ctx.accounts.escrow_reward_token_account.as_ref().map(|account| account.to_account_info()),
+    offeree_reward_token_account: ctx.accounts.offeree_reward_token_account.as_ref().map(|account| account.to_account_info()),
+    counter: &mut ctx.accounts.counter,
+    owner_profile: &mut ctx.accounts.owner_profile,
+    offeree_profile: &mut ctx.accounts.offeree_profile,
+    offeree: ctx.accounts.offeree.to_account_info(),
+    token_program: ctx.accounts.token_program.to_account_info(),
+    badge: ctx.accounts.badge.as_deref_mut().map(|badge| &mut **badge),
+    badge_mint: BadgeMint {
+      payer: Some(ctx.accounts.offeree.to_account_info()),
+      recipient: ctx.accounts.offeree.to_account_info(),
+      recipient_token_account: ctx.accounts.offeree_badge_token_account.as_ref().map(|account| account.to_account_info()),
+      mint: ctx.accounts.badge_mint.as_ref().map(|account| account.to_account_info()),
+      config: ctx.accounts.config.to_account_info(),
+      token_program: ctx.accounts.token_2022_program.as_ref().map(|program| program.to_account_info()),
+      associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+      system_program: ctx.accounts.system_program.to_account_info(),
+    },
+  }.complete(ctx.bumps.badge, ctx.bumps.badge_mint, ctx.accounts.config.bump)?;
+
+  emit_cpi!(QuestCompleted {
+    quest_id: ctx.accounts.quest.id,
+    actor: Some(ctx.accounts.offeree.key()),
+    old_status: QuestStatus::Taken as u8,
+    new_status: ctx.accounts.quest.status,
+    offeree_staked,
+    reward,
+    slot: Clock::get()?.slot,
+  });
+
+  Ok(())
+}
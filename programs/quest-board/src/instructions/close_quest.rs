@@ -1,15 +1,21 @@
-// closing an unpublished quest - retrieve owner's placement fee (if any) and return governance token
+// closing an unpublished quest - retrieve owner's placement fee (if any, minus the decay fee) and return governance token
+// and the reward, unless it was paid out on completion
 
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer, CloseAccount};
 
 use crate::{
+  events::QuestClosed,
   state::Quest,
+  state::QuestStatus,
   state::QuestError,
   state::Config,
+  state::ConfigError,
+  state::UserProfile,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CloseQuest<'info> {
   #[account(
@@ -21,7 +27,7 @@ pub struct CloseQuest<'info> {
     bump = quest.bump,
     has_one = owner,
     close = owner,
-    constraint = (quest.status == 0 || quest.status == 7) @ QuestError::CloseNotAllowed,
+    constraint = (quest.is(QuestStatus::Unpublished) || quest.is(QuestStatus::Complete)) @ QuestError::CloseNotAllowed,
   )]
   pub quest: Box<Account<'info, Quest>>,
 
@@ -39,6 +45,25 @@ pub struct CloseQuest<'info> {
   )]
   pub owner_token_account: Account<'info, TokenAccount>,
 
+  #[account(
+    constraint = quest.reward_mint == Some(reward_mint.key()) @ QuestError::InvalidRewardMint,
+  )]
+  pub reward_mint: Option<Box<Account<'info, Mint>>>,
+
+  #[account(
+    mut,
+    associated_token::mint = reward_mint,
+    associated_token::authority = quest,
+  )]
+  pub escrow_reward_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+  #[account(
+    mut,
+    associated_token::mint = reward_mint,
+    associated_token::authority = owner,
+  )]
+  pub owner_reward_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
   #[account(
     seeds = [
       b"config",
@@ -46,13 +71,27 @@ pub struct CloseQuest<'info> {
     bump = config.bump,
     has_one = token_mint,
     has_one = treasury,
+    constraint = !config.exits_paused() @ ConfigError::ProgramPaused,
   )]
   pub config: Account<'info, Config>,
 
+  #[account(
+    init_if_needed,
+    payer = owner,
+    seeds = [
+      b"profile",
+      owner.key().as_ref(),
+    ],
+    bump,
+    space = UserProfile::len(),
+  )]
+  pub owner_profile: Box<Account<'info, UserProfile>>,
+
   #[account(mut)]
   /// CHECK: has_one in the config account
   pub treasury: UncheckedAccount<'info>,
 
+  #[account(mut)]
   pub owner: Signer<'info>,
 
   pub token_mint: Account<'info, Mint>,
@@ -70,6 +109,8 @@ pub fn close_quest_handler(ctx: Context<CloseQuest>) -> Result<()> {
   let id = quest.id.key();
   let quest_bump = quest.bump.to_le_bytes();
   let escrow = &mut ctx.accounts.escrow_token_account;
+  let old_status = quest.status;
+  let owner_amount = escrow.amount;
   let owner = &mut ctx.accounts.owner;
 
   let seeds = vec![
@@ -107,7 +148,39 @@ pub fn close_quest_handler(ctx: Context<CloseQuest>) -> Result<()> {
 
   anchor_spl::token::close_account(cpi_ctx)?;
 
-  ctx.accounts.quest.close_account()?;
+  quest.refund_reward(
+    &quest.to_account_info(),
+    &ctx.accounts.owner.to_account_info(),
+    ctx.accounts.owner_reward_token_account.as_ref().map(|account| account.to_account_info()),
+    ctx.accounts.escrow_reward_token_account.as_ref().map(|account| account.to_account_info()),
+    &ctx.accounts.token_program.to_account_info(),
+  )?;
+
+  let owner_profile = &mut ctx.accounts.owner_profile;
+  owner_profile.bump = ctx.bumps.owner_profile;
+  owner_profile.wallet = ctx.accounts.owner.key();
+
+  if quest.is(QuestStatus::Unpublished) {
+    owner_profile.quests_closed += 1;
+  }
+
+  ctx.accounts.quest.transition(QuestStatus::Closed)?;
+
+  let decay_fee = ctx.accounts.quest.close_account(
+    &ctx.accounts.quest.to_account_info(),
+    &ctx.accounts.treasury.to_account_info(),
+    &ctx.accounts.config,
+  )?;
+
+  emit_cpi!(QuestClosed {
+    quest_id: ctx.accounts.quest.id,
+    actor: Some(ctx.accounts.owner.key()),
+    old_status,
+    owner_amount,
+    offeree_amount: 0,
+    decay_fee,
+    slot: Clock::get()?.slot,
+  });
 
   Ok(())
 }
@@ -0,0 +1,102 @@
+// both parties must sign this
+
+// marking the current milestone complete - the offeree gets its slice of the reward, the quest stays taken
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Mint};
+
+use crate::{
+  events::MilestoneCompleted,
+  state::Quest,
+  state::QuestStatus,
+  state::QuestError,
+  state::Config,
+  state::ConfigError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CompleteMilestone<'info> {
+  #[account(
+    mut,
+    seeds = [
+      b"quest",
+      quest.id.key().as_ref(),
+    ],
+    bump = quest.bump,
+    has_one = owner,
+    realloc = Quest::len(),
+    realloc::payer = owner,
+    realloc::zero = false,
+    constraint = quest.is(QuestStatus::Taken) @ QuestError::CompleteNotAllowed,
+    constraint = quest.offeree == Some(offeree.key()) @ QuestError::NotQuestOfferee,
+    constraint = (quest.current_milestone as usize) < quest.milestones.len() @ QuestError::MilestoneNotAllowed,
+  )]
+  pub quest: Box<Account<'info, Quest>>,
+
+  #[account(
+    constraint = quest.reward_mint == Some(reward_mint.key()) @ QuestError::InvalidRewardMint,
+  )]
+  pub reward_mint: Option<Box<Account<'info, Mint>>>,
+
+  #[account(
+    mut,
+    associated_token::mint = reward_mint,
+    associated_token::authority = quest,
+  )]
+  pub escrow_reward_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+  #[account(
+    mut,
+    associated_token::mint = reward_mint,
+    associated_token::authority = offeree,
+  )]
+  pub offeree_reward_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+  #[account(
+    seeds = [
+      b"config",
+    ],
+    bump = config.bump,
+    constraint = !config.is_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Account<'info, Config>,
+
+  #[account(mut)]
+  pub owner: Signer<'info>,
+
+  #[account(mut)]
+  pub offeree: Signer<'info>,
+
+  pub system_program: Program<'info, System>,
+  pub token_program: Program<'info, Token>,
+}
+
+pub fn complete_milestone_handler(ctx: Context<CompleteMilestone>) -> Result<()> {
+
+  let quest = &mut ctx.accounts.quest;
+  let current_milestone = quest.current_milestone as usize;
+  let reward = quest.milestones[current_milestone].reward;
+
+  quest.release_reward(
+    &quest.to_account_info(),
+    &ctx.accounts.offeree.to_account_info(),
+    ctx.accounts.offeree_reward_token_account.as_ref().map(|account| account.to_account_info()),
+    ctx.accounts.escrow_reward_token_account.as_ref().map(|account| account.to_account_info()),
+    &ctx.accounts.token_program.to_account_info(),
+    reward,
+  )?;
+
+  quest.milestones[current_milestone].completed = true;
+  quest.current_milestone += 1;
+
+  emit_cpi!(MilestoneCompleted {
+    quest_id: quest.id,
+    actor: Some(ctx.accounts.owner.key()),
+    milestone: current_milestone as u8,
+    reward,
+    slot: Clock::get()?.slot,
+  });
+
+  Ok(())
+}
@@ -1,18 +1,27 @@
 // both parties must sign this
 
 // closing a complete quest - return both owner and offeree's staked governance token, and owner's placement fee (if any)
+// the offeree gets the reward, and optionally a non-transferable completion badge
 
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
+use anchor_spl::token_2022::Token2022;
 
 use crate::{
+  events::QuestCompleted,
   state::Quest,
+  state::QuestStatus,
   state::QuestError,
   state::Config,
+  state::ConfigError,
   state::Counter,
+  state::UserProfile,
+  state::Badge,
+  state::BadgeMint,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CompleteQuest<'info> {
   #[account(
@@ -23,7 +32,10 @@ pub struct CompleteQuest<'info> {
     ],
     bump = quest.bump,
     has_one = owner,
-    constraint = quest.status == 3 @ QuestError::CompleteNotAllowed,
+    realloc = Quest::len(),
+    realloc::payer = owner,
+    realloc::zero = false,
+    constraint = quest.is(QuestStatus::Taken) @ QuestError::CompleteNotAllowed,
   )]
   pub quest: Box<Account<'info, Quest>>,
 
@@ -41,6 +53,25 @@ pub struct CompleteQuest<'info> {
   )]
   pub offeree_token_account: Account<'info, TokenAccount>,
 
+  #[account(
+    constraint = quest.reward_mint == Some(reward_mint.key()) @ QuestError::InvalidRewardMint,
+  )]
+  pub reward_mint: Option<Box<Account<'info, Mint>>>,
+
+  #[account(
+    mut,
+    associated_token::mint = reward_mint,
+    associated_token::authority = quest,
+  )]
+  pub escrow_reward_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+  #[account(
+    mut,
+    associated_token::mint = reward_mint,
+    associated_token::authority = offeree,
+  )]
+  pub offeree_reward_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
   #[account(
     seeds = [
       b"config",
@@ -48,6 +79,7 @@ pub struct CompleteQuest<'info> {
     bump = config.bump,
     has_one = token_mint,
     has_one = treasury,
+    constraint = !config.is_paused() @ ConfigError::ProgramPaused,
   )]
   pub config: Account<'info, Config>,
 
@@ -60,52 +92,191 @@ pub struct CompleteQuest<'info> {
   )]
   pub counter: Account<'info, Counter>,
 
+  #[account(
+    init_if_needed,
+    payer = offeree,
+    seeds = [
+      b"profile",
+      owner.key().as_ref(),
+    ],
+    bump,
+    space = UserProfile::len(),
+  )]
+  pub owner_profile: Box<Account<'info, UserProfile>>,
+
+  #[account(
+    init_if_needed,
+    payer = offeree,
+    seeds = [
+      b"profile",
+      offeree.key().as_ref(),
+    ],
+    bump,
+    space = UserProfile::len(),
+  )]
+  pub offeree_profile: Box<Account<'info, UserProfile>>,
+
   #[account(mut)]
   /// CHECK: has_one in the config account
   pub treasury: UncheckedAccount<'info>,
 
+  #[account(mut)]
   pub owner: Signer<'info>,
 
+  #[account(mut)]
   pub offeree: Signer<'info>,
 
   pub token_mint: Account<'info, Mint>,
 
+  // the completion badge is minted only if these accounts are passed
+
+  #[account(
+    init,
+    payer = offeree,
+    seeds = [
+      b"badge",
+      quest.key().as_ref(),
+    ],
+    bump,
+    space = Badge::len()
+  )]
+  pub badge: Option<Box<Account<'info, Badge>>>,
+
+  #[account(
+    mut,
+    seeds = [
+      b"badge_mint",
+      quest.key().as_ref(),
+    ],
+    bump,
+  )]
+  /// CHECK: created as a non-transferable Token-2022 mint when minting the badge
+  pub badge_mint: Option<UncheckedAccount<'info>>,
+
+  #[account(mut)]
+  /// CHECK: the offeree's associated token account of the badge mint, created by the associated token program
+  pub offeree_badge_token_account: Option<UncheckedAccount<'info>>,
+
+  pub token_2022_program: Option<Program<'info, Token2022>>,
+
   pub system_program: Program<'info, System>,
   pub associated_token_program: Program<'info, AssociatedToken>,
   pub token_program: Program<'info, Token>,
   pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn complete_quest_handler(ctx: Context<CompleteQuest>) -> Result<()> {
+/// The part of completing a Quest shared by complete_quest and claim_completion:
+/// the offeree gets back their stake, the reward and the badge (if asked for).
+pub struct Completion<'a, 'info> {
+  pub quest: &'a mut Account<'info, Quest>,
+  pub escrow_token_account: AccountInfo<'info>,
+  pub offeree_token_account: AccountInfo<'info>,
+  pub escrow_reward_token_account: Option<AccountInfo<'info>>,
+  pub offeree_reward_token_account: Option<AccountInfo<'info>>,
+  pub counter: &'a mut Counter,
+  pub owner_profile: &'a mut UserProfile,
+  pub offeree_profile: &'a mut UserProfile,
+  pub offeree: AccountInfo<'info>,
+  pub token_program: AccountInfo<'info>,
+  pub badge: Option<&'a mut Badge>,
+  pub badge_mint: BadgeMint<'info>,
+}
 
-  let quest = &mut ctx.accounts.quest;
-  let id = quest.id.key();
-  let quest_bump = quest.bump.to_le_bytes();
-  let counter = &mut ctx.accounts.counter;
-
-  let offeree_staked = quest.offeree_staked.unwrap();
-
-  let seeds = vec![
-    b"quest".as_ref(), 
-    id.as_ref(),
-    quest_bump.as_ref()
-  ];
-  let signer = vec![seeds.as_slice()];
-
-  let transfer_ix = Transfer {
-    from: ctx.accounts.escrow_token_account.to_account_info(),
-    to: ctx.accounts.offeree_token_account.to_account_info(),
-    authority: quest.to_account_info(),
-  };
-  let cpi_program = ctx.accounts.token_program.to_account_info();
-  let cpi_ctx = CpiContext::new_with_signer(cpi_program, transfer_ix, signer.as_slice());
-  transfer(cpi_ctx, offeree_staked)?;
-
-  quest.status = 7; 
-  counter.posts_taken -= 1;
-  counter.posts_completed += 1;
+impl<'a, 'info> Completion<'a, 'info> {
+  /// Moves the Quest to complete, returns the offeree's stake and the reward paid out.
+  pub fn complete(self, badge_bump: Option<u8>, badge_mint_bump: Option<u8>, config_bump: u8) -> Result<(u64, u64)> {
+    let quest = self.quest;
+    let id = quest.id.key();
+    let quest_bump = quest.bump.to_le_bytes();
 
-  Ok(())
+    let offeree_staked = quest.offeree_staked.unwrap();
+
+    let seeds = vec![
+      b"quest".as_ref(),
+      id.as_ref(),
+      quest_bump.as_ref()
+    ];
+    let signer = vec![seeds.as_slice()];
+
+    let transfer_ix = Transfer {
+      from: self.escrow_token_account,
+      to: self.offeree_token_account,
+      authority: quest.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(self.token_program.clone(), transfer_ix, signer.as_slice());
+    transfer(cpi_ctx, offeree_staked)?;
+
+    // pay out the reward
+    let reward = quest.remaining_reward();
+    quest.release_reward(
+      &quest.to_account_info(),
+      &self.offeree,
+      self.offeree_reward_token_account,
+      self.escrow_reward_token_account,
+      &self.token_program,
+      reward,
+    )?;
+
+    // mint the completion badge, if asked for
+    if let Some(badge) = self.badge {
+      self.badge_mint.mint(badge, badge_bump.unwrap(), quest, badge_mint_bump.unwrap(), config_bump)?;
+    }
+
+    self.owner_profile.quests_completed += 1;
+    self.offeree_profile.quests_completed += 1;
+
+    quest.completed_timestamp = Some(Clock::get()?.slot);
+    quest.transition(QuestStatus::Complete)?;
+    self.counter.posts_taken -= 1;
+    self.counter.posts_completed += 1;
+
+    Ok((offeree_staked, reward))
+  }
 }
 
+pub fn complete_quest_handler(ctx: Context<CompleteQuest>) -> Result<()> {
+
+  let owner_profile = &mut ctx.accounts.owner_profile;
+  owner_profile.bump = ctx.bumps.owner_profile;
+  owner_profile.wallet = ctx.accounts.owner.key();
+
+  let offeree_profile = &mut ctx.accounts.offeree_profile;
+  offeree_profile.bump = ctx.bumps.offeree_profile;
+  offeree_profile.wallet = ctx.accounts.offeree.key();
+
+  let (offeree_staked, reward) = Completion {
+    quest: &mut ctx.accounts.quest,
+    escrow_token_account: ctx.accounts.escrow_token_account.to_account_info(),
+    offeree_token_account: ctx.accounts.offeree_token_account.to_account_info(),
+    escrow_reward_token_account: ctx.accounts.escrow_reward_token_account.as_ref().map(|account| account.to_account_info()),
+    offeree_reward_token_account: ctx.accounts.offeree_reward_token_account.as_ref().map(|account| account.to_account_info()),
+    counter: &mut ctx.accounts.counter,
+    owner_profile: &mut ctx.accounts.owner_profile,
+    offeree_profile: &mut ctx.accounts.offeree_profile,
+    offeree: ctx.accounts.offeree.to_account_info(),
+    token_program: ctx.accounts.token_program.to_account_info(),
+    badge: ctx.accounts.badge.as_deref_mut().map(|badge| &mut **badge),
+    badge_mint: BadgeMint {
+      payer: Some(ctx.accounts.offeree.to_account_info()),
+      recipient: ctx.accounts.offeree.to_account_info(),
+      recipient_token_account: ctx.accounts.offeree_badge_token_account.as_ref().map(|account| account.to_account_info()),
+      mint: ctx.accounts.badge_mint.as_ref().map(|account| account.to_account_info()),
+      config: ctx.accounts.config.to_account_info(),
+      token_program: ctx.accounts.token_2022_program.as_ref().map(|program| program.to_account_info()),
+      associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+      system_program: ctx.accounts.system_program.to_account_info(),
+    },
+  }.complete(ctx.bumps.badge, ctx.bumps.badge_mint, ctx.accounts.config.bump)?;
 
+  emit_cpi!(QuestCompleted {
+    quest_id: ctx.accounts.quest.id,
+    actor: Some(ctx.accounts.owner.key()),
+    old_status: QuestStatus::Taken as u8,
+    new_status: ctx.accounts.quest.status,
+    offeree_staked,
+    reward,
+    slot: Clock::get()?.slot,
+  });
+
+  Ok(())
+}
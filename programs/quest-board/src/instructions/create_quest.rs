@@ -2,7 +2,13 @@ use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
 
-use crate::{state::Config, state::Quest, state::QuestError};
+use crate::{events::QuestCreated, state::Config, state::ConfigError, state::Quest, state::QuestStatus, state::QuestError, state::Milestone, state::MAX_MILESTONES, state::QUEST_VERSION, state::UserProfile};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct MilestoneParams {
+  pub reward: u64,
+  pub details_hash: [u8; 32],
+}
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
 pub struct CreateQuestParams {
@@ -10,8 +16,14 @@ pub struct CreateQuestParams {
   pub min_stake_required: u64,
   pub placement_paid: u64,
   pub details_hash: [u8; 32],
+  pub accept_deadline: Option<u64>,
+  pub delivery_deadline: Option<u64>,
+  pub reward: u64,
+  pub milestones: Vec<MilestoneParams>,
+  pub min_reputation: Option<i64>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(params: CreateQuestParams)]
 pub struct CreateQuest<'info> {
@@ -36,6 +48,18 @@ pub struct CreateQuest<'info> {
   )]
   pub escrow_token_account: Account<'info, TokenAccount>,
 
+  #[account(
+    init_if_needed,
+    payer = owner,
+    seeds = [
+      b"profile",
+      owner.key().as_ref(),
+    ],
+    bump,
+    space = UserProfile::len(),
+  )]
+  pub owner_profile: Box<Account<'info, UserProfile>>,
+
   #[account(
     mut,
     associated_token::mint = token_mint,
@@ -46,6 +70,29 @@ pub struct CreateQuest<'info> {
 
   pub token_mint: Account<'info, Mint>,
 
+  // the reward is paid in SOL unless the reward mint accounts are passed
+
+  #[account(
+    constraint = config.reward_mint == Some(reward_mint.key()) @ QuestError::InvalidRewardMint,
+  )]
+  pub reward_mint: Option<Box<Account<'info, Mint>>>,
+
+  #[account(
+    init_if_needed,
+    payer = owner,
+    associated_token::mint = reward_mint,
+    associated_token::authority = quest
+  )]
+  pub escrow_reward_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+  #[account(
+    mut,
+    associated_token::mint = reward_mint,
+    associated_token::authority = owner,
+    constraint = owner_reward_token_account.amount >= params.reward @ QuestError::NotEnoughTokenBalance,
+  )]
+  pub owner_reward_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
   #[account(mut)]
   /// CHECK: has_one in the config account
   pub treasury: UncheckedAccount<'info>,
@@ -57,6 +104,7 @@ pub struct CreateQuest<'info> {
     bump = config.bump,
     has_one = token_mint,
     has_one = treasury,
+    constraint = !config.is_paused() @ ConfigError::ProgramPaused,
   )]
   pub config: Box<Account<'info, Config>>,
 
@@ -78,12 +126,13 @@ pub fn create_quest_handler(ctx: Context<CreateQuest>, params: CreateQuestParams
   let owner = &mut ctx.accounts.owner;
   let config = &ctx.accounts.config;
   let treasury = &ctx.accounts.treasury;
+  let slot = Clock::get()?.slot;
 
   quest.bump = ctx.bumps.quest;
-  quest.status = 0;
+  quest.status = QuestStatus::Unpublished as u8;
   quest.id = ctx.accounts.id.key();
   quest.owner = owner.key();
-  quest.timestamp = Clock::get()?.slot;
+  quest.timestamp = slot;
   quest.staked = params.stake_amount;
   quest.min_stake_required = params.min_stake_required;
   quest.placement_paid = params.placement_paid;
@@ -94,12 +143,63 @@ pub fn create_quest_handler(ctx: Context<CreateQuest>, params: CreateQuestParams
   quest.owner_votes = None;
   quest.offeree_votes = None;
   quest.abstained_votes = None;
+  quest.dispute_raised_by = None;
+  quest.dispute_timestamp = None;
+  quest.accept_deadline = params.accept_deadline;
+  quest.delivery_deadline = params.delivery_deadline;
+  quest.deliverable_hash = None;
+  quest.deliverable_timestamp = None;
+  quest.reward = params.reward;
+  quest.reward_mint = ctx.accounts.reward_mint.as_ref().map(|reward_mint| reward_mint.key());
+  quest.current_milestone = 0;
+  quest.min_reputation = params.min_reputation;
+  quest.completed_timestamp = None;
+  quest.version = QUEST_VERSION;
+  quest.offer_nonce = 0;
+  quest.milestones = params.milestones.iter().map(|milestone| Milestone {
+    reward: milestone.reward,
+    details_hash: milestone.details_hash,
+    completed: false,
+  }).collect();
+
+  let owner_profile = &mut ctx.accounts.owner_profile;
+  owner_profile.bump = ctx.bumps.owner_profile;
+  owner_profile.wallet = owner.key();
+  owner_profile.quests_posted += 1;
 
   // min_stake_required should not exceed the staked amount
   if quest.min_stake_required > quest.staked {
     return Err(QuestError::MinStakeRequiredExceedsStaked.into());
   }
 
+  // deadlines should be in the future, delivery comes after acceptance
+  if params.accept_deadline.is_some_and(|deadline| deadline <= slot) ||
+    params.delivery_deadline.is_some_and(|deadline| deadline <= slot) {
+    return Err(QuestError::InvalidDeadline.into());
+  }
+
+  if let (Some(accept_deadline), Some(delivery_deadline)) = (params.accept_deadline, params.delivery_deadline) {
+    if delivery_deadline <= accept_deadline {
+      return Err(QuestError::InvalidDeadline.into());
+    }
+  }
+
+  // milestones split the whole reward
+  if quest.milestones.len() > MAX_MILESTONES {
+    return Err(QuestError::TooManyMilestones.into());
+  }
+
+  if !quest.milestones.is_empty() {
+    // rewards overflowing a u64 cannot add up to the reward either
+    let total = quest.milestones.iter()
+      .try_fold(0u64, |total, milestone| total.checked_add(milestone.reward))
+      .ok_or(QuestError::InvalidMilestoneRewards)?;
+
+    if total != quest.reward {
+      return Err(QuestError::InvalidMilestoneRewards.into());
+    }
+  }
+
   // pay base_fee to treasury
   let base_fee = config.base_fee;
 
@@ -141,11 +241,52 @@ pub fn create_quest_handler(ctx: Context<CreateQuest>, params: CreateQuestParams
   let cpi_accounts = Transfer {
     from: ctx.accounts.owner_token_account.to_account_info(),
     to: ctx.accounts.escrow_token_account.to_account_info(),
-    authority: ctx.accounts.owner.to_account_info(),
+    authority: owner.to_account_info(),
   };
   let cpi_program = ctx.accounts.token_program.to_account_info();
   let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
   transfer(cpi_ctx, quest.staked)?;
 
+  // escrow the reward, SOL stays in the quest account next to the placement fee
+  match (&ctx.accounts.escrow_reward_token_account, &ctx.accounts.owner_reward_token_account) {
+    (Some(escrow_reward_token_account), Some(owner_reward_token_account)) => {
+      let cpi_accounts = Transfer {
+        from: owner_reward_token_account.to_account_info(),
+        to: escrow_reward_token_account.to_account_info(),
+        authority: owner.to_account_info(),
+      };
+      let cpi_program = ctx.accounts.token_program.to_account_info();
+      let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+      transfer(cpi_ctx, quest.reward)?;
+    },
+    (None, None) if quest.reward_mint.is_none() => {
+      let ix = anchor_lang::solana_program::system_instruction::transfer(
+        &owner.key(),
+        &quest.key(),
+        quest.reward,
+      );
+
+      anchor_lang::solana_program::program::invoke(
+        &ix,
+        &[
+          owner.to_account_info(),
+          quest.to_account_info(),
+          ctx.accounts.system_program.to_account_info(),
+        ],
+      )?;
+    },
+    _ => return Err(QuestError::RewardAccountsMissing.into()),
+  }
+
+  emit_cpi!(QuestCreated {
+    quest_id: quest.id,
+    actor: Some(owner.key()),
+    new_status: quest.status,
+    staked: quest.staked,
+    placement_paid: quest.placement_paid,
+    reward: quest.reward,
+    slot,
+  });
+
   Ok(())
 }
@@ -0,0 +1,156 @@
+// permissionless, anyone can expire a quest past its deadline
+// open quest past the accept_deadline - unpublished, the owner gets back the staked governance token
+//   the account stays, the owner can close it to retrieve the placement fee (if any), the reward and the rent
+// taken quest past the delivery_deadline without a deliverable - the offeree's stake is forfeited to the owner,
+//   and the quest goes back to unpublished so the owner can publish or close it
+// either way the deadlines are cleared, a republished quest has none
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
+
+use crate::{
+  events::QuestStatusChanged,
+  state::Quest,
+  state::QuestStatus,
+  state::QuestError,
+  state::Config,
+  state::ConfigError,
+  state::Counter,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExpireQuest<'info> {
+  #[account(
+    mut,
+    seeds = [
+      b"quest",
+      quest.id.key().as_ref(),
+    ],
+    bump = quest.bump,
+    has_one = owner,
+    constraint = quest.is_migrated() @ QuestError::QuestNotMigrated,
+    constraint = (quest.is(QuestStatus::Open) || quest.is(QuestStatus::Taken)) @ QuestError::ExpireNotAllowed,
+  )]
+  pub quest: Box<Account<'info, Quest>>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = quest,
+  )]
+  pub escrow_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = owner,
+  )]
+  pub owner_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    seeds = [
+      b"config",
+    ],
+    bump = config.bump,
+    has_one = token_mint,
+    constraint = !config.is_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Box<Account<'info, Config>>,
+
+  #[account(
+    mut,
+    seeds = [
+      b"counter",
+    ],
+    bump = counter.bump,
+  )]
+  pub counter: Account<'info, Counter>,
+
+  /// CHECK: has_one in the quest account
+  pub owner: UncheckedAccount<'info>,
+
+  pub token_mint: Account<'info, Mint>,
+
+  pub system_program: Program<'info, System>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_program: Program<'info, Token>,
+  pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn expire_quest_handler(ctx: Context<ExpireQuest>) -> Result<()> {
+
+  let quest = &ctx.accounts.quest;
+  let id = quest.id.key();
+  let quest_bump = quest.bump.to_le_bytes();
+  let escrow = &ctx.accounts.escrow_token_account;
+  let slot = Clock::get()?.slot;
+  let status = QuestStatus::try_from(quest.status)?;
+
+  let deadline = match status {
+    QuestStatus::Open => quest.accept_deadline,
+    _ => quest.delivery_deadline,
+  };
+
+  match deadline {
+    Some(deadline) if slot > deadline && quest.deliverable_timestamp.is_none() => {},
+    _ => return Err(QuestError::ExpireNotAllowed.into()),
+  }
+
+  let seeds = vec![
+    b"quest".as_ref(),
+    id.as_ref(),
+    quest_bump.as_ref()
+  ];
+  let signer = vec![seeds.as_slice()];
+
+  // open: the owner's stake is all the escrow holds, taken: only the offeree's stake is forfeited
+  let amount = match status {
+    QuestStatus::Open => escrow.amount,
+    _ => quest.offeree_staked.unwrap(),
+  };
+
+  let transfer_ix = Transfer {
+    from: escrow.to_account_info(),
+    to: ctx.accounts.owner_token_account.to_account_info(),
+    authority: quest.to_account_info(),
+  };
+  let cpi_ctx = CpiContext::new_with_signer(
+    ctx.accounts.token_program.to_account_info(),
+    transfer_ix,
+    signer.as_slice()
+  );
+  transfer(cpi_ctx, amount)?;
+
+  let counter = &mut ctx.accounts.counter;
+  let quest = &mut ctx.accounts.quest;
+
+  if status == QuestStatus::Open {
+    quest.staked = 0;
+    quest.min_stake_required = 0;
+    counter.posts_open -= 1;
+  } else {
+    quest.offeree = None;
+    quest.offeree_staked = None;
+    quest.offeree_proposal_hash = None;
+    quest.accepted_timestamp = None;
+    quest.delivery_deadline = None;
+    counter.posts_taken -= 1;
+  }
+
+  // update_quest cannot change the deadlines, a republished quest must not be expirable right away
+  quest.accept_deadline = None;
+
+  quest.transition(QuestStatus::Unpublished)?;
+
+  emit_cpi!(QuestStatusChanged {
+    quest_id: id,
+    actor: None,
+    old_status: status as u8,
+    new_status: quest.status,
+    slot,
+  });
+
+  Ok(())
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::{state::Config, state::Counter};
+use crate::{program::QuestBoard, state::Config, state::ConfigError, state::Counter, state::MAX_ABANDON_PENALTY};
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
 pub struct InitializeParams {
@@ -13,6 +13,9 @@ pub struct InitializeParams {
   pub dispute_duration: u64,
   pub staked_vote_power_start: u64,
   pub unstaked_vote_unlock_interval: u64,
+  pub deliverable_review_period: u64,
+  pub abandon_penalty: u16,
+  pub review_period: u64,
 }
 
 #[derive(Accounts)]
@@ -44,15 +47,15 @@ pub struct Initialize<'info> {
   #[account(mut)]
   pub authority: Signer<'info>,
 
-  // #[account(
-  //   constraint = program.programdata_address()? == Some(program_data.key()) @ ConfigError::InvalidProgramData,
-  // )]
-  // pub program: Program<'info, QuestBoard>,
+  #[account(
+    constraint = program.programdata_address()? == Some(program_data.key()) @ ConfigError::InvalidProgramData,
+  )]
+  pub program: Program<'info, QuestBoard>,
 
-  // #[account(
-  //   constraint = program_data.upgrade_authority_address == Some(authority.key()) @ ConfigError::InvalidUpdateAuthority,
-  // )]
-  // pub program_data: Box<Account<'info, ProgramData>>,
+  #[account(
+    constraint = program_data.upgrade_authority_address == Some(authority.key()) @ ConfigError::InvalidUpdateAuthority,
+  )]
+  pub program_data: Box<Account<'info, ProgramData>>,
 
   pub system_program: Program<'info, System>,
 }
@@ -72,6 +75,17 @@ pub fn initialize_handler(ctx: Context<Initialize>, params: InitializeParams) ->
   config.dispute_duration = params.dispute_duration;
   config.staked_vote_power_start = params.staked_vote_power_start;
   config.unstaked_vote_unlock_interval = params.unstaked_vote_unlock_interval;
+  config.deliverable_review_period = params.deliverable_review_period;
+  config.pending_authority = None;
+  config.paused = 0;
+  config.reward_mint = None;
+  config.abandon_penalty = params.abandon_penalty;
+  config.abandon_penalty_to_treasury = false;
+  config.review_period = params.review_period;
+
+  if params.abandon_penalty > MAX_ABANDON_PENALTY {
+    return Err(ConfigError::InvalidAbandonPenalty.into());
+  }
 
   counter.bump = ctx.bumps.counter;
   counter.posts_open = 0;
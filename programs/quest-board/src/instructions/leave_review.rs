@@ -0,0 +1,112 @@
+// either the owner or the offeree of a complete quest can review the other party, once
+// the review period ends after config.review_period slots, or when the quest is closed
+
+use anchor_lang::prelude::*;
+
+use crate::{
+  state::Quest,
+  state::QuestStatus,
+  state::QuestError,
+  state::Config,
+  state::ConfigError,
+  state::Review,
+  state::ReviewError,
+  state::UserProfile,
+};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct LeaveReviewParams {
+  pub rating: u8,
+  pub review_hash: [u8; 32],
+}
+
+#[derive(Accounts)]
+#[instruction(params: LeaveReviewParams)]
+pub struct LeaveReview<'info> {
+  #[account(
+    seeds = [
+      b"quest",
+      quest.id.key().as_ref(),
+    ],
+    bump = quest.bump,
+    constraint = quest.is(QuestStatus::Complete) @ ReviewError::ReviewNotAllowed,
+    constraint = (
+      quest.owner == reviewer.key() ||
+      quest.offeree == Some(reviewer.key())
+    ) @ QuestError::NotQuestParticipant,
+  )]
+  pub quest: Box<Account<'info, Quest>>,
+
+  #[account(
+    init,
+    payer = reviewer,
+    seeds = [
+      b"review",
+      quest.key().as_ref(),
+      reviewer.key().as_ref(),
+    ],
+    bump,
+    space = Review::len()
+  )]
+  pub review: Box<Account<'info, Review>>,
+
+  #[account(
+    mut,
+    seeds = [
+      b"profile",
+      reviewee_profile.wallet.as_ref(),
+    ],
+    bump = reviewee_profile.bump,
+    constraint = reviewee_profile.wallet != reviewer.key() @ ReviewError::InvalidReviewee,
+    constraint = (
+      quest.owner == reviewee_profile.wallet ||
+      quest.offeree == Some(reviewee_profile.wallet)
+    ) @ ReviewError::InvalidReviewee,
+  )]
+  pub reviewee_profile: Box<Account<'info, UserProfile>>,
+
+  #[account(
+    seeds = [
+      b"config",
+    ],
+    bump = config.bump,
+    constraint = !config.is_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Account<'info, Config>,
+
+  #[account(mut)]
+  pub reviewer: Signer<'info>,
+
+  pub system_program: Program<'info, System>,
+}
+
+pub fn leave_review_handler(ctx: Context<LeaveReview>, params: LeaveReviewParams) -> Result<()> {
+
+  let quest = &ctx.accounts.quest;
+  let review = &mut ctx.accounts.review;
+  let reviewee_profile = &mut ctx.accounts.reviewee_profile;
+  let slot = Clock::get()?.slot;
+
+  if !(1..=5).contains(&params.rating) {
+    return Err(ReviewError::InvalidRating.into());
+  }
+
+  let completed_timestamp = quest.completed_timestamp.ok_or(ReviewError::ReviewNotAllowed)?;
+
+  if slot > completed_timestamp.saturating_add(ctx.accounts.config.review_period) {
+    return Err(ReviewError::ReviewPeriodOver.into());
+  }
+
+  review.bump = ctx.bumps.review;
+  review.quest = quest.key();
+  review.reviewer = ctx.accounts.reviewer.key();
+  review.reviewee = reviewee_profile.wallet;
+  review.rating = params.rating;
+  review.review_hash = params.review_hash;
+  review.timestamp = slot;
+
+  reviewee_profile.rating_sum += params.rating as u64;
+  reviewee_profile.rating_count += 1;
+
+  Ok(())
+}
@@ -0,0 +1,54 @@
+// only the owner signs this, moving a legacy quest account to the current layout
+// the account is reallocated to Quest::len(), the owner pays the extra rent
+// the owner's own instructions grow a legacy account the same way, the instructions signed by anyone else
+// (offeree, voters, cranks) reject it with QuestNotMigrated until the owner migrates it
+
+use anchor_lang::prelude::*;
+
+use crate::{
+  state::Config,
+  state::ConfigError,
+  state::Quest,
+  state::QuestError,
+  state::QUEST_VERSION,
+};
+
+#[derive(Accounts)]
+pub struct MigrateQuest<'info> {
+  #[account(
+    mut,
+    seeds = [
+      b"quest",
+      quest.id.key().as_ref(),
+    ],
+    bump = quest.bump,
+    has_one = owner @ QuestError::NotQuestOwner,
+    realloc = Quest::len(),
+    realloc::payer = owner,
+    realloc::zero = false,
+    constraint = !quest.is_migrated() @ QuestError::AlreadyMigrated,
+  )]
+  pub quest: Box<Account<'info, Quest>>,
+
+  #[account(
+    seeds = [
+      b"config",
+    ],
+    bump = config.bump,
+    constraint = !config.is_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Box<Account<'info, Config>>,
+
+  #[account(mut)]
+  pub owner: Signer<'info>,
+
+  pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_quest_handler(ctx: Context<MigrateQuest>) -> Result<()> {
+  let quest = &mut ctx.accounts.quest;
+
+  quest.version = QUEST_VERSION;
+
+  Ok(())
+}
@@ -1,17 +1,63 @@
 mod initialize;
+mod update_config;
+mod propose_authority;
+mod accept_authority;
+mod set_pause;
 mod create_quest;
 mod publish_quest;
 mod unpublish_quest;
 mod update_quest;
+mod migrate_quest;
 mod close_quest;
 mod accept_quest;
+mod accept_signed_offer;
+mod apply_to_quest;
+mod approve_application;
+mod withdraw_application;
+mod complete_milestone;
 mod complete_quest;
+mod cancel_quest;
+mod abandon_quest;
+mod expire_quest;
+mod submit_deliverable;
+mod claim_completion;
+mod leave_review;
+mod raise_dispute;
+mod stake_vote_power;
+mod unstake_vote_power;
+mod withdraw_staked_votes;
+mod vote_on_dispute;
+mod resolve_dispute;
+mod settle_dispute;
 
 pub use initialize::*;
+pub use update_config::*;
+pub use propose_authority::*;
+pub use accept_authority::*;
+pub use set_pause::*;
 pub use create_quest::*;
 pub use publish_quest::*;
 pub use unpublish_quest::*;
 pub use update_quest::*;
+pub use migrate_quest::*;
 pub use close_quest::*;
 pub use accept_quest::*;
-pub use complete_quest::*;
\ No newline at end of file
+pub use accept_signed_offer::*;
+pub use apply_to_quest::*;
+pub use approve_application::*;
+pub use withdraw_application::*;
+pub use complete_milestone::*;
+pub use complete_quest::*;
+pub use cancel_quest::*;
+pub use abandon_quest::*;
+pub use expire_quest::*;
+pub use submit_deliverable::*;
+pub use claim_completion::*;
+pub use leave_review::*;
+pub use raise_dispute::*;
+pub use stake_vote_power::*;
+pub use unstake_vote_power::*;
+pub use withdraw_staked_votes::*;
+pub use vote_on_dispute::*;
+pub use resolve_dispute::*;
+pub use settle_dispute::*;
\ No newline at end of file
@@ -0,0 +1,37 @@
+// first step of the authority handover, the new authority has to accept (see accept_authority)
+// proposing again replaces the pending authority, proposing None cancels the handover
+
+use anchor_lang::prelude::*;
+use crate::{state::Config, state::ConfigError};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct ProposeAuthorityParams {
+  pub new_authority: Option<Pubkey>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: ProposeAuthorityParams)]
+pub struct ProposeAuthority<'info> {
+
+  #[account(
+    mut,
+    seeds = [
+      b"config",
+    ],
+    bump = config.bump,
+    has_one = authority @ ConfigError::InvalidUpdateAuthority,
+  )]
+  pub config: Box<Account<'info, Config>>,
+
+  pub authority: Signer<'info>,
+
+  pub system_program: Program<'info, System>,
+}
+
+pub fn propose_authority_handler(ctx: Context<ProposeAuthority>, params: ProposeAuthorityParams) -> Result<()> {
+  let config = &mut ctx.accounts.config;
+
+  config.pending_authority = params.new_authority;
+
+  Ok(())
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use crate::{state::Counter, state::Quest, state::QuestError};
+use crate::{events::QuestStatusChanged, state::Config, state::ConfigError, state::Counter, state::Quest, state::QuestStatus, state::QuestError};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct PublishQuest<'info> {
 
@@ -12,7 +13,10 @@ pub struct PublishQuest<'info> {
     ],
     bump = quest.bump,
     has_one = owner,
-    constraint = quest.status == 0 @ QuestError::QuestAlreadyPublished
+    realloc = Quest::len(),
+    realloc::payer = owner,
+    realloc::zero = false,
+    constraint = quest.is(QuestStatus::Unpublished) @ QuestError::QuestAlreadyPublished
   )]
   pub quest: Account<'info, Quest>,
 
@@ -25,6 +29,16 @@ pub struct PublishQuest<'info> {
   )]
   pub counter: Account<'info, Counter>,
 
+  #[account(
+    seeds = [
+      b"config",
+    ],
+    bump = config.bump,
+    constraint = !config.is_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Box<Account<'info, Config>>,
+
+  #[account(mut)]
   pub owner: Signer<'info>,
 
   pub system_program: Program<'info, System>,
@@ -34,8 +48,16 @@ pub fn publish_quest_handler(ctx: Context<PublishQuest>) -> Result<()> {
   let quest = &mut ctx.accounts.quest;
   let counter = &mut ctx.accounts.counter;
 
-  quest.status = 1;
+  quest.transition(QuestStatus::Open)?;
   counter.posts_open += 1;
 
+  emit_cpi!(QuestStatusChanged {
+    quest_id: quest.id,
+    actor: Some(ctx.accounts.owner.key()),
+    old_status: QuestStatus::Unpublished as u8,
+    new_status: quest.status,
+    slot: Clock::get()?.slot,
+  });
+
   Ok(())
 }
\ No newline at end of file
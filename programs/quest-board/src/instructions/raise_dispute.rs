@@ -0,0 +1,124 @@
+// either the owner or the offeree can raise a dispute on a taken quest
+// the raiser pays for the profile of a party without one, resolve_dispute updates both
+
+use anchor_lang::prelude::*;
+
+use crate::{
+  events::QuestStatusChanged,
+  state::Quest,
+  state::QuestStatus,
+  state::QuestError,
+  state::Config,
+  state::ConfigError,
+  state::Counter,
+  state::UserProfile,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RaiseDispute<'info> {
+  #[account(
+    mut,
+    seeds = [
+      b"quest",
+      quest.id.key().as_ref(),
+    ],
+    bump = quest.bump,
+    has_one = owner,
+    constraint = quest.is_migrated() @ QuestError::QuestNotMigrated,
+    constraint = quest.is(QuestStatus::Taken) @ QuestError::DisputeNotAllowed,
+    constraint = quest.offeree == Some(offeree.key()) @ QuestError::DisputeNotAllowed,
+    constraint = (
+      quest.owner == signer.key() ||
+      quest.offeree == Some(signer.key())
+    ) @ QuestError::NotQuestParticipant,
+  )]
+  pub quest: Box<Account<'info, Quest>>,
+
+  #[account(
+    mut,
+    seeds = [
+      b"counter",
+    ],
+    bump = counter.bump,
+  )]
+  pub counter: Account<'info, Counter>,
+
+  #[account(
+    seeds = [
+      b"config",
+    ],
+    bump = config.bump,
+    constraint = !config.is_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Box<Account<'info, Config>>,
+
+  #[account(
+    init_if_needed,
+    payer = signer,
+    seeds = [
+      b"profile",
+      owner.key().as_ref(),
+    ],
+    bump,
+    space = UserProfile::len(),
+  )]
+  pub owner_profile: Box<Account<'info, UserProfile>>,
+
+  #[account(
+    init_if_needed,
+    payer = signer,
+    seeds = [
+      b"profile",
+      offeree.key().as_ref(),
+    ],
+    bump,
+    space = UserProfile::len(),
+  )]
+  pub offeree_profile: Box<Account<'info, UserProfile>>,
+
+  /// CHECK: has_one in the quest account
+  pub owner: UncheckedAccount<'info>,
+
+  /// CHECK: constrained to the offeree of the quest account
+  pub offeree: UncheckedAccount<'info>,
+
+  #[account(mut)]
+  pub signer: Signer<'info>,
+
+  pub system_program: Program<'info, System>,
+}
+
+pub fn raise_dispute_handler(ctx: Context<RaiseDispute>) -> Result<()> {
+
+  let quest = &mut ctx.accounts.quest;
+  let counter = &mut ctx.accounts.counter;
+
+  quest.dispute_raised_by = Some(ctx.accounts.signer.key());
+  quest.dispute_timestamp = Some(Clock::get()?.slot);
+  quest.owner_votes = Some(0);
+  quest.offeree_votes = Some(0);
+  quest.abstained_votes = Some(0);
+
+  let owner_profile = &mut ctx.accounts.owner_profile;
+  owner_profile.bump = ctx.bumps.owner_profile;
+  owner_profile.wallet = ctx.accounts.owner.key();
+
+  let offeree_profile = &mut ctx.accounts.offeree_profile;
+  offeree_profile.bump = ctx.bumps.offeree_profile;
+  offeree_profile.wallet = ctx.accounts.offeree.key();
+
+  quest.transition(QuestStatus::Dispute)?;
+  counter.posts_taken -= 1;
+  counter.posts_in_dispute += 1;
+
+  emit_cpi!(QuestStatusChanged {
+    quest_id: quest.id,
+    actor: Some(ctx.accounts.signer.key()),
+    old_status: QuestStatus::Taken as u8,
+    new_status: quest.status,
+    slot: Clock::get()?.slot,
+  });
+
+  Ok(())
+}
@@ -1,3 +1,122 @@
-// check threshold
-// check dispute_duration
-// closing a resolved quest (coming from a dispute) - complex retrieval, todo
\ No newline at end of file
+// permissionless, anyone can resolve the dispute once dispute_duration has passed
+// the profiles of both parties exist since raise_dispute
+// falling short of vote_threshold, or having no clear majority, results to a draw
+// closing a resolved quest (coming from a dispute) happens in settle_dispute
+
+use anchor_lang::prelude::*;
+
+use crate::{
+  events::QuestStatusChanged,
+  state::Quest,
+  state::QuestStatus,
+  state::QuestError,
+  state::Config,
+  state::ConfigError,
+  state::Counter,
+  state::UserProfile,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+  #[account(
+    mut,
+    seeds = [
+      b"quest",
+      quest.id.key().as_ref(),
+    ],
+    bump = quest.bump,
+    has_one = owner,
+    constraint = quest.is_migrated() @ QuestError::QuestNotMigrated,
+    constraint = quest.is(QuestStatus::Dispute) @ QuestError::ResolveNotAllowed,
+    constraint = quest.offeree == Some(offeree.key()) @ QuestError::ResolveNotAllowed,
+  )]
+  pub quest: Box<Account<'info, Quest>>,
+
+  #[account(
+    seeds = [
+      b"config",
+    ],
+    bump = config.bump,
+    constraint = !config.is_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Account<'info, Config>,
+
+  #[account(
+    mut,
+    seeds = [
+      b"counter",
+    ],
+    bump = counter.bump,
+  )]
+  pub counter: Account<'info, Counter>,
+
+  #[account(
+    mut,
+    seeds = [
+      b"profile",
+      owner.key().as_ref(),
+    ],
+    bump = owner_profile.bump,
+  )]
+  pub owner_profile: Box<Account<'info, UserProfile>>,
+
+  #[account(
+    mut,
+    seeds = [
+      b"profile",
+      offeree.key().as_ref(),
+    ],
+    bump = offeree_profile.bump,
+  )]
+  pub offeree_profile: Box<Account<'info, UserProfile>>,
+
+  /// CHECK: has_one in the quest account
+  pub owner: UncheckedAccount<'info>,
+
+  /// CHECK: constrained to the offeree of the quest account
+  pub offeree: UncheckedAccount<'info>,
+
+  pub system_program: Program<'info, System>,
+}
+
+pub fn resolve_dispute_handler(ctx: Context<ResolveDispute>) -> Result<()> {
+
+  let quest = &mut ctx.accounts.quest;
+  let config = &ctx.accounts.config;
+  let counter = &mut ctx.accounts.counter;
+
+  if Clock::get()?.slot <= quest.dispute_timestamp.unwrap().saturating_add(config.dispute_duration) {
+    return Err(QuestError::DisputeStillActive.into());
+  }
+
+  let outcome = quest.dispute_outcome(config.vote_threshold);
+  quest.transition(outcome)?;
+
+  let owner_profile = &mut ctx.accounts.owner_profile;
+  let offeree_profile = &mut ctx.accounts.offeree_profile;
+
+  match outcome {
+    QuestStatus::OwnerWon => {
+      owner_profile.disputes_won += 1;
+      offeree_profile.disputes_lost += 1;
+    },
+    QuestStatus::OffereeWon => {
+      owner_profile.disputes_lost += 1;
+      offeree_profile.disputes_won += 1;
+    },
+    _ => {},
+  }
+  counter.posts_in_dispute -= 1;
+  counter.posts_resolved += 1;
+
+  emit_cpi!(QuestStatusChanged {
+    quest_id: quest.id,
+    actor: None,
+    old_status: QuestStatus::Dispute as u8,
+    new_status: quest.status,
+    slot: Clock::get()?.slot,
+  });
+
+  Ok(())
+}
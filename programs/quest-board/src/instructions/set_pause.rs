@@ -0,0 +1,40 @@
+// emergency switch, exits can stay open so users can still pull their escrowed tokens
+
+use anchor_lang::prelude::*;
+use crate::{state::Config, state::ConfigError, state::PAUSE_QUESTS, state::PAUSE_EXITS};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct SetPauseParams {
+  pub paused: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(params: SetPauseParams)]
+pub struct SetPause<'info> {
+
+  #[account(
+    mut,
+    seeds = [
+      b"config",
+    ],
+    bump = config.bump,
+    has_one = authority @ ConfigError::InvalidUpdateAuthority,
+  )]
+  pub config: Box<Account<'info, Config>>,
+
+  pub authority: Signer<'info>,
+
+  pub system_program: Program<'info, System>,
+}
+
+pub fn set_pause_handler(ctx: Context<SetPause>, params: SetPauseParams) -> Result<()> {
+  let config = &mut ctx.accounts.config;
+
+  if params.paused & !(PAUSE_QUESTS | PAUSE_EXITS) != 0 {
+    return Err(ConfigError::InvalidPauseFlags.into());
+  }
+
+  config.paused = params.paused;
+
+  Ok(())
+}
@@ -0,0 +1,278 @@
+// permissionless, anyone can settle a resolved quest
+// closing a resolved quest - the winner takes both staked governance tokens, on a draw each side gets their stake back
+// the disputed reward (the current milestone, if any) goes to the offeree if they won, the rest back to the owner
+// the owner retrieves the placement fee (if any, minus the decay fee) and the rent
+// a dispute won by the offeree can mint them a badge, paid by whoever settles, if the badge accounts are passed
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer, CloseAccount};
+use anchor_spl::token_2022::Token2022;
+
+use crate::{
+  events::QuestClosed,
+  state::Quest,
+  state::QuestStatus,
+  state::QuestError,
+  state::Config,
+  state::ConfigError,
+  state::Badge,
+  state::BadgeMint,
+  state::BadgeError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleDispute<'info> {
+  #[account(
+    mut,
+    seeds = [
+      b"quest",
+      quest.id.key().as_ref(),
+    ],
+    bump = quest.bump,
+    has_one = owner,
+    close = owner,
+    constraint = QuestStatus::try_from(quest.status)?.is_resolved() @ QuestError::SettleNotAllowed,
+    constraint = quest.offeree == Some(offeree.key()) @ QuestError::SettleNotAllowed,
+  )]
+  pub quest: Box<Account<'info, Quest>>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = quest,
+  )]
+  pub escrow_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = owner,
+  )]
+  pub owner_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = offeree,
+  )]
+  pub offeree_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    constraint = quest.reward_mint == Some(reward_mint.key()) @ QuestError::InvalidRewardMint,
+  )]
+  pub reward_mint: Option<Box<Account<'info, Mint>>>,
+
+  #[account(
+    mut,
+    associated_token::mint = reward_mint,
+    associated_token::authority = quest,
+  )]
+  pub escrow_reward_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+  #[account(
+    mut,
+    associated_token::mint = reward_mint,
+    associated_token::authority = owner,
+  )]
+  pub owner_reward_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+  #[account(
+    mut,
+    associated_token::mint = reward_mint,
+    associated_token::authority = offeree,
+  )]
+  pub offeree_reward_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+  #[account(
+    seeds = [
+      b"config",
+    ],
+    bump = config.bump,
+    has_one = token_mint,
+    has_one = treasury,
+    constraint = !config.exits_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Account<'info, Config>,
+
+  #[account(mut)]
+  /// CHECK: has_one in the config account
+  pub treasury: UncheckedAccount<'info>,
+
+  #[account(mut)]
+  /// CHECK: has_one in the quest account
+  pub owner: UncheckedAccount<'info>,
+
+  #[account(mut)]
+  /// CHECK: constrained to the offeree of the quest account
+  pub offeree: UncheckedAccount<'info>,
+
+  pub token_mint: Account<'info, Mint>,
+
+  // the badge of a dispute won by the offeree, minted only if these accounts are passed
+
+  #[account(
+    init,
+    payer = payer,
+    seeds = [
+      b"badge",
+      quest.key().as_ref(),
+    ],
+    bump,
+    space = Badge::len()
+  )]
+  pub badge: Option<Box<Account<'info, Badge>>>,
+
+  #[account(
+    mut,
+    seeds = [
+      b"badge_mint",
+      quest.key().as_ref(),
+    ],
+    bump,
+  )]
+  /// CHECK: created as a non-transferable Token-2022 mint when minting the badge
+  pub badge_mint: Option<UncheckedAccount<'info>>,
+
+  #[account(mut)]
+  /// CHECK: the offeree's associated token account of the badge mint, created by the associated token program
+  pub offeree_badge_token_account: Option<UncheckedAccount<'info>>,
+
+  pub token_2022_program: Option<Program<'info, Token2022>>,
+
+  #[account(mut)]
+  pub payer: Option<Signer<'info>>,
+
+  pub system_program: Program<'info, System>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_program: Program<'info, Token>,
+  pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn settle_dispute_handler(ctx: Context<SettleDispute>) -> Result<()> {
+
+  let quest = &ctx.accounts.quest;
+  let id = quest.id.key();
+  let quest_bump = quest.bump.to_le_bytes();
+  let escrow = &ctx.accounts.escrow_token_account;
+
+  let seeds = vec![
+    b"quest".as_ref(),
+    id.as_ref(),
+    quest_bump.as_ref()
+  ];
+  let signer = vec![seeds.as_slice()];
+
+  let status = QuestStatus::try_from(quest.status)?;
+
+  let offeree_amount = match status {
+    QuestStatus::OwnerWon => 0,
+    QuestStatus::OffereeWon => escrow.amount,
+    _ => quest.offeree_staked.unwrap(),
+  };
+  let owner_amount = escrow.amount - offeree_amount;
+
+  if owner_amount > 0 {
+    let transfer_ix = Transfer {
+      from: escrow.to_account_info(),
+      to: ctx.accounts.owner_token_account.to_account_info(),
+      authority: quest.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+      ctx.accounts.token_program.to_account_info(),
+      transfer_ix,
+      signer.as_slice()
+    );
+    transfer(cpi_ctx, owner_amount)?;
+  }
+
+  if offeree_amount > 0 {
+    let transfer_ix = Transfer {
+      from: escrow.to_account_info(),
+      to: ctx.accounts.offeree_token_account.to_account_info(),
+      authority: quest.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+      ctx.accounts.token_program.to_account_info(),
+      transfer_ix,
+      signer.as_slice()
+    );
+    transfer(cpi_ctx, offeree_amount)?;
+  }
+
+  let close_ix = CloseAccount {
+    account: escrow.to_account_info(),
+    destination: ctx.accounts.owner.to_account_info(),
+    authority: quest.to_account_info(),
+  };
+
+  let cpi_ctx = CpiContext::new_with_signer(
+    ctx.accounts.token_program.to_account_info(),
+    close_ix,
+    signer.as_slice(),
+  );
+
+  anchor_spl::token::close_account(cpi_ctx)?;
+
+  // the disputed reward goes to the offeree only if they won
+  let offeree_reward = match status {
+    QuestStatus::OffereeWon => quest.disputed_reward(),
+    _ => 0,
+  };
+
+  quest.release_reward(
+    &quest.to_account_info(),
+    &ctx.accounts.offeree.to_account_info(),
+    ctx.accounts.offeree_reward_token_account.as_ref().map(|account| account.to_account_info()),
+    ctx.accounts.escrow_reward_token_account.as_ref().map(|account| account.to_account_info()),
+    &ctx.accounts.token_program.to_account_info(),
+    offeree_reward,
+  )?;
+
+  quest.refund_reward(
+    &quest.to_account_info(),
+    &ctx.accounts.owner.to_account_info(),
+    ctx.accounts.owner_reward_token_account.as_ref().map(|account| account.to_account_info()),
+    ctx.accounts.escrow_reward_token_account.as_ref().map(|account| account.to_account_info()),
+    &ctx.accounts.token_program.to_account_info(),
+  )?;
+
+  // only a dispute won by the offeree earns a badge, minted if asked for
+  match (&mut ctx.accounts.badge, status) {
+    (Some(badge), QuestStatus::OffereeWon) => {
+      BadgeMint {
+        payer: ctx.accounts.payer.as_ref().map(|payer| payer.to_account_info()),
+        recipient: ctx.accounts.offeree.to_account_info(),
+        recipient_token_account: ctx.accounts.offeree_badge_token_account.as_ref().map(|account| account.to_account_info()),
+        mint: ctx.accounts.badge_mint.as_ref().map(|account| account.to_account_info()),
+        config: ctx.accounts.config.to_account_info(),
+        token_program: ctx.accounts.token_2022_program.as_ref().map(|program| program.to_account_info()),
+        associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+      }.mint(badge, ctx.bumps.badge.unwrap(), quest, ctx.bumps.badge_mint.unwrap(), ctx.accounts.config.bump)?;
+    },
+    (Some(_), _) => return Err(BadgeError::BadgeNotEarned.into()),
+    (None, _) => {},
+  }
+
+  ctx.accounts.quest.transition(QuestStatus::Closed)?;
+
+  let decay_fee = ctx.accounts.quest.close_account(
+    &ctx.accounts.quest.to_account_info(),
+    &ctx.accounts.treasury.to_account_info(),
+    &ctx.accounts.config,
+  )?;
+
+  emit_cpi!(QuestClosed {
+    quest_id: id,
+    actor: None,
+    old_status: status as u8,
+    owner_amount,
+    offeree_amount,
+    decay_fee,
+    slot: Clock::get()?.slot,
+  });
+
+  Ok(())
+}
@@ -1 +1,100 @@
 // staking will reset the timer where the user can vote again (update timestamp + staked_vote_start)
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
+
+use crate::{
+  state::Config,
+  state::ConfigError,
+  state::VotePower,
+  state::VotePowerError,
+};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct StakeVotePowerParams {
+  pub amount: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(params: StakeVotePowerParams)]
+pub struct StakeVotePower<'info> {
+
+  #[account(
+    init_if_needed,
+    payer = owner,
+    seeds = [
+      b"vote_power",
+      owner.key().as_ref(),
+    ],
+    bump,
+    space = VotePower::len(),
+    constraint = vote_power.unstake_timestamp.is_none() @ VotePowerError::UnstakeInProgress,
+  )]
+  pub vote_power: Box<Account<'info, VotePower>>,
+
+  #[account(
+    init_if_needed,
+    payer = owner,
+    associated_token::mint = token_mint,
+    associated_token::authority = vote_power
+  )]
+  pub vault_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = owner,
+    constraint = owner_token_account.amount >= params.amount @ VotePowerError::NotEnoughTokenBalance,
+  )]
+  pub owner_token_account: Account<'info, TokenAccount>,
+
+  pub token_mint: Account<'info, Mint>,
+
+  #[account(
+    seeds = [
+      b"config"
+    ],
+    bump = config.bump,
+    has_one = token_mint,
+    constraint = !config.is_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Box<Account<'info, Config>>,
+
+  #[account(mut)]
+  pub owner: Signer<'info>,
+
+  pub system_program: Program<'info, System>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_program: Program<'info, Token>,
+  pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn stake_vote_power_handler(ctx: Context<StakeVotePower>, params: StakeVotePowerParams) -> Result<()> {
+
+  if params.amount == 0 {
+    return Err(VotePowerError::InvalidStakeAmount.into());
+  }
+
+  let vote_power = &mut ctx.accounts.vote_power;
+
+  vote_power.bump = ctx.bumps.vote_power;
+  vote_power.owner = ctx.accounts.owner.key();
+  vote_power.vault = ctx.accounts.vault_token_account.key();
+  vote_power.staked += params.amount;
+
+  // restaking resets the warm-up, see Config::staked_vote_power_start
+  vote_power.staked_timestamp = Clock::get()?.slot;
+
+  // transfer governance tokens
+  let cpi_accounts = Transfer {
+    from: ctx.accounts.owner_token_account.to_account_info(),
+    to: ctx.accounts.vault_token_account.to_account_info(),
+    authority: ctx.accounts.owner.to_account_info(),
+  };
+  let cpi_program = ctx.accounts.token_program.to_account_info();
+  let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+  transfer(cpi_ctx, params.amount)?;
+
+  Ok(())
+}
@@ -0,0 +1,72 @@
+// submitting (or resubmitting) a deliverable starts the owner's review period
+// if the owner neither completes nor disputes the quest in time, the offeree can claim completion
+
+use anchor_lang::prelude::*;
+
+use crate::{
+  events::DeliverableSubmitted,
+  state::Quest,
+  state::QuestStatus,
+  state::QuestError,
+  state::Config,
+  state::ConfigError,
+};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct SubmitDeliverableParams {
+  pub deliverable_hash: [u8; 32],
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: SubmitDeliverableParams)]
+pub struct SubmitDeliverable<'info> {
+  #[account(
+    mut,
+    seeds = [
+      b"quest",
+      quest.id.key().as_ref(),
+    ],
+    bump = quest.bump,
+    constraint = quest.is_migrated() @ QuestError::QuestNotMigrated,
+    constraint = quest.is(QuestStatus::Taken) @ QuestError::CompleteNotAllowed,
+    constraint = quest.offeree == Some(offeree.key()) @ QuestError::NotQuestOfferee,
+  )]
+  pub quest: Box<Account<'info, Quest>>,
+
+  #[account(
+    seeds = [
+      b"config",
+    ],
+    bump = config.bump,
+    constraint = !config.is_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Box<Account<'info, Config>>,
+
+  pub offeree: Signer<'info>,
+
+  pub system_program: Program<'info, System>,
+}
+
+pub fn submit_deliverable_handler(ctx: Context<SubmitDeliverable>, params: SubmitDeliverableParams) -> Result<()> {
+
+  let quest = &mut ctx.accounts.quest;
+  let slot = Clock::get()?.slot;
+
+  if quest.delivery_deadline.is_some_and(|deadline| slot > deadline) {
+    return Err(QuestError::QuestExpired.into());
+  }
+
+  quest.deliverable_hash = Some(params.deliverable_hash);
+  quest.deliverable_timestamp = Some(slot);
+
+  emit_cpi!(DeliverableSubmitted {
+    quest_id: quest.id,
+    actor: Some(ctx.accounts.offeree.key()),
+    deliverable_hash: params.deliverable_hash,
+    review_ends: slot.saturating_add(ctx.accounts.config.deliverable_review_period),
+    slot,
+  });
+
+  Ok(())
+}
@@ -1 +1,52 @@
-// when unstake process is initiated, calculate the amount of tokens that can be unlocked per interval
\ No newline at end of file
+// unstaking freezes the vote power, the tokens are then released gradually (see withdraw_staked_votes)
+
+use anchor_lang::prelude::*;
+
+use crate::{
+  state::Config,
+  state::ConfigError,
+  state::VotePower,
+  state::VotePowerError,
+};
+
+#[derive(Accounts)]
+pub struct UnstakeVotePower<'info> {
+
+  #[account(
+    mut,
+    seeds = [
+      b"vote_power",
+      owner.key().as_ref(),
+    ],
+    bump = vote_power.bump,
+    has_one = owner,
+    constraint = vote_power.unstake_timestamp.is_none() @ VotePowerError::UnstakeInProgress,
+    constraint = vote_power.staked > 0 @ VotePowerError::NothingStaked,
+  )]
+  pub vote_power: Box<Account<'info, VotePower>>,
+
+  #[account(
+    seeds = [
+      b"config",
+    ],
+    bump = config.bump,
+    constraint = !config.exits_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Box<Account<'info, Config>>,
+
+  pub owner: Signer<'info>,
+
+  pub system_program: Program<'info, System>,
+}
+
+pub fn unstake_vote_power_handler(ctx: Context<UnstakeVotePower>) -> Result<()> {
+
+  let vote_power = &mut ctx.accounts.vote_power;
+
+  vote_power.unstaked = vote_power.staked;
+  vote_power.withdrawn = 0;
+  vote_power.staked = 0;
+  vote_power.unstake_timestamp = Some(Clock::get()?.slot);
+
+  Ok(())
+}
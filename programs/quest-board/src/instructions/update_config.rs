@@ -0,0 +1,111 @@
+// the governance token (token_mint) cannot be changed, existing escrows depend on it
+// a default (all zeros) reward_mint clears it, only SOL rewards are accepted then
+
+use anchor_lang::prelude::*;
+use crate::{state::Config, state::ConfigError, state::MAX_ABANDON_PENALTY};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct UpdateConfigParams {
+  pub treasury: Option<Pubkey>,
+  pub base_fee: Option<u64>,
+  pub decay_fee: Option<u64>,
+  pub decay_start: Option<u64>,
+  pub vote_threshold: Option<u64>,
+  pub dispute_duration: Option<u64>,
+  pub staked_vote_power_start: Option<u64>,
+  pub unstaked_vote_unlock_interval: Option<u64>,
+  pub deliverable_review_period: Option<u64>,
+  pub reward_mint: Option<Pubkey>,
+  pub abandon_penalty: Option<u16>,
+  pub abandon_penalty_to_treasury: Option<bool>,
+  pub review_period: Option<u64>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: UpdateConfigParams)]
+pub struct UpdateConfig<'info> {
+
+  #[account(
+    mut,
+    seeds = [
+      b"config",
+    ],
+    bump = config.bump,
+    has_one = authority @ ConfigError::InvalidUpdateAuthority,
+  )]
+  pub config: Box<Account<'info, Config>>,
+
+  pub authority: Signer<'info>,
+
+  pub system_program: Program<'info, System>,
+}
+
+pub fn update_config_handler(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+  let config = &mut ctx.accounts.config;
+
+  if let Some(treasury) = params.treasury {
+    if treasury == Pubkey::default() {
+      return Err(ConfigError::InvalidTreasury.into());
+    }
+    config.treasury = treasury;
+  }
+
+  if let Some(base_fee) = params.base_fee {
+    config.base_fee = base_fee;
+  }
+
+  if let Some(decay_fee) = params.decay_fee {
+    config.decay_fee = decay_fee;
+  }
+
+  if let Some(decay_start) = params.decay_start {
+    config.decay_start = decay_start;
+  }
+
+  if let Some(vote_threshold) = params.vote_threshold {
+    config.vote_threshold = vote_threshold;
+  }
+
+  if let Some(dispute_duration) = params.dispute_duration {
+    if dispute_duration == 0 {
+      return Err(ConfigError::InvalidDisputeDuration.into());
+    }
+    config.dispute_duration = dispute_duration;
+  }
+
+  if let Some(staked_vote_power_start) = params.staked_vote_power_start {
+    config.staked_vote_power_start = staked_vote_power_start;
+  }
+
+  if let Some(unstaked_vote_unlock_interval) = params.unstaked_vote_unlock_interval {
+    config.unstaked_vote_unlock_interval = unstaked_vote_unlock_interval;
+  }
+
+  if let Some(deliverable_review_period) = params.deliverable_review_period {
+    config.deliverable_review_period = deliverable_review_period;
+  }
+
+  if let Some(reward_mint) = params.reward_mint {
+    if reward_mint == config.token_mint {
+      return Err(ConfigError::InvalidRewardMint.into());
+    }
+    config.reward_mint = if reward_mint == Pubkey::default() { None } else { Some(reward_mint) };
+  }
+
+  if let Some(abandon_penalty) = params.abandon_penalty {
+    if abandon_penalty > MAX_ABANDON_PENALTY {
+      return Err(ConfigError::InvalidAbandonPenalty.into());
+    }
+    config.abandon_penalty = abandon_penalty;
+  }
+
+  if let Some(abandon_penalty_to_treasury) = params.abandon_penalty_to_treasury {
+    config.abandon_penalty_to_treasury = abandon_penalty_to_treasury;
+  }
+
+  if let Some(review_period) = params.review_period {
+    config.review_period = review_period;
+  }
+
+  Ok(())
+}
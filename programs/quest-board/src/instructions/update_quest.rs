@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::{state::Quest, state::QuestError};
+use crate::{state::Config, state::ConfigError, state::Quest, state::QuestStatus, state::QuestError};
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
 pub struct UpdateQuestParams {
@@ -19,10 +19,23 @@ pub struct UpdateQuest<'info> {
     ],
     bump = quest.bump,
     has_one = owner,
-    constraint = quest.status == 0 @ QuestError::UpdateNotAllowed
+    realloc = Quest::len(),
+    realloc::payer = owner,
+    realloc::zero = false,
+    constraint = quest.is(QuestStatus::Unpublished) @ QuestError::UpdateNotAllowed
   )]
   pub quest: Account<'info, Quest>,
 
+  #[account(
+    seeds = [
+      b"config",
+    ],
+    bump = config.bump,
+    constraint = !config.is_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Box<Account<'info, Config>>,
+
+  #[account(mut)]
   pub owner: Signer<'info>,
 
   pub system_program: Program<'info, System>,
@@ -31,12 +44,11 @@ pub struct UpdateQuest<'info> {
 pub fn update_quest_handler(ctx: Context<UpdateQuest>, params: UpdateQuestParams) -> Result<()> {
   let quest = &mut ctx.accounts.quest;
 
-  if params.details_hash.is_some() {
-    quest.details_hash = params.details_hash.unwrap();
+  if let Some(details_hash) = params.details_hash {
+    quest.details_hash = details_hash;
   }
 
-  if params.min_stake_required.is_some() {
-    let min_stake_required = params.min_stake_required.unwrap();
+  if let Some(min_stake_required) = params.min_stake_required {
     if min_stake_required > quest.staked {
       return Err(QuestError::MinStakeRequiredExceedsStaked.into());
     }
@@ -1,2 +1,126 @@
-// status needs to be greater than 12
-// note: owner and offeree of the quests can't cast their votes
\ No newline at end of file
+// voting is only open while the quest is in dispute, until dispute_duration passes
+// note: owner and offeree of the quests can't cast their votes
+
+use anchor_lang::prelude::*;
+
+use crate::{
+  events::VoteCast,
+  state::Config,
+  state::ConfigError,
+  state::Quest,
+  state::QuestError,
+  state::QuestStatus,
+  state::QuestVote,
+  state::QuestVoteError,
+  state::VotePower,
+};
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct VoteOnDisputeParams {
+  pub vote: u8,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: VoteOnDisputeParams)]
+pub struct VoteOnDispute<'info> {
+
+  #[account(
+    mut,
+    seeds = [
+      b"quest",
+      quest.id.key().as_ref(),
+    ],
+    bump = quest.bump,
+    constraint = quest.is_migrated() @ QuestError::QuestNotMigrated,
+    constraint = quest.is(QuestStatus::Dispute) @ QuestVoteError::DisputeNotActive,
+    constraint = (
+      quest.owner != voter.key() &&
+      quest.offeree != Some(voter.key())
+    ) @ QuestVoteError::PartyCannotVote,
+  )]
+  pub quest: Box<Account<'info, Quest>>,
+
+  #[account(
+    init,
+    payer = voter,
+    seeds = [
+      b"quest_vote",
+      quest.key().as_ref(),
+      voter.key().as_ref(),
+    ],
+    bump,
+    space = QuestVote::len()
+  )]
+  pub quest_vote: Box<Account<'info, QuestVote>>,
+
+  #[account(
+    seeds = [
+      b"vote_power",
+      voter.key().as_ref(),
+    ],
+    bump = vote_power.bump,
+    constraint = vote_power.unstake_timestamp.is_none() @ QuestVoteError::VotePowerFrozen,
+    constraint = vote_power.staked > 0 @ QuestVoteError::NoVotePower,
+  )]
+  pub vote_power: Box<Account<'info, VotePower>>,
+
+  #[account(
+    seeds = [
+      b"config",
+    ],
+    bump = config.bump,
+    constraint = !config.is_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Box<Account<'info, Config>>,
+
+  #[account(mut)]
+  pub voter: Signer<'info>,
+
+  pub system_program: Program<'info, System>,
+}
+
+pub fn vote_on_dispute_handler(ctx: Context<VoteOnDispute>, params: VoteOnDisputeParams) -> Result<()> {
+
+  let quest = &mut ctx.accounts.quest;
+  let quest_vote = &mut ctx.accounts.quest_vote;
+  let vote_power = &ctx.accounts.vote_power;
+  let config = &ctx.accounts.config;
+  let slot = Clock::get()?.slot;
+
+  if slot < vote_power.staked_timestamp.saturating_add(config.staked_vote_power_start) {
+    return Err(QuestVoteError::VotePowerNotActive.into());
+  }
+
+  if slot > quest.dispute_timestamp.unwrap().saturating_add(config.dispute_duration) {
+    return Err(QuestVoteError::VotingClosed.into());
+  }
+
+  let power = vote_power.staked;
+
+  let votes = match params.vote {
+    0 => &mut quest.owner_votes,
+    1 => &mut quest.offeree_votes,
+    2 => &mut quest.abstained_votes,
+    _ => return Err(QuestVoteError::InvalidVote.into()),
+  };
+
+  *votes = Some(votes.unwrap_or(0).checked_add(power).ok_or(QuestVoteError::VotesOverflow)?);
+
+  quest_vote.bump = ctx.bumps.quest_vote;
+  quest_vote.quest = quest.key();
+  quest_vote.voter = ctx.accounts.voter.key();
+  quest_vote.vote = params.vote;
+  quest_vote.power = power;
+  quest_vote.timestamp = slot;
+
+  emit_cpi!(VoteCast {
+    quest_id: quest.id,
+    actor: Some(ctx.accounts.voter.key()),
+    vote: params.vote,
+    power,
+    slot,
+  });
+
+  Ok(())
+}
@@ -0,0 +1,113 @@
+// only the applicant signs this, returns the staked governance token and closes the application
+// the quest itself is not needed, it may already be taken or closed
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer, CloseAccount};
+
+use crate::{
+  events::ApplicationWithdrawn,
+  state::Application,
+  state::Config,
+  state::ConfigError,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawApplication<'info> {
+
+  #[account(
+    mut,
+    seeds = [
+      b"application",
+      application.quest.as_ref(),
+      offeree.key().as_ref(),
+    ],
+    bump = application.bump,
+    has_one = offeree,
+    close = offeree,
+  )]
+  pub application: Box<Account<'info, Application>>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = application,
+  )]
+  pub application_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = offeree,
+  )]
+  pub offeree_token_account: Account<'info, TokenAccount>,
+
+  pub token_mint: Account<'info, Mint>,
+
+  #[account(mut)]
+  pub offeree: Signer<'info>,
+
+  #[account(
+    seeds = [b"config"],
+    bump = config.bump,
+    has_one = token_mint,
+    constraint = !config.exits_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Account<'info, Config>,
+
+  pub system_program: Program<'info, System>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_program: Program<'info, Token>,
+}
+
+pub fn withdraw_application_handler(ctx: Context<WithdrawApplication>) -> Result<()> {
+
+  let application = &ctx.accounts.application;
+  let quest = application.quest;
+  let offeree = ctx.accounts.offeree.key();
+  let application_bump = application.bump.to_le_bytes();
+  let application_token_account = &ctx.accounts.application_token_account;
+  let amount = application_token_account.amount;
+
+  let seeds = vec![
+    b"application".as_ref(),
+    quest.as_ref(),
+    offeree.as_ref(),
+    application_bump.as_ref()
+  ];
+  let signer = vec![seeds.as_slice()];
+
+  let transfer_ix = Transfer {
+    from: application_token_account.to_account_info(),
+    to: ctx.accounts.offeree_token_account.to_account_info(),
+    authority: application.to_account_info(),
+  };
+  let cpi_ctx = CpiContext::new_with_signer(
+    ctx.accounts.token_program.to_account_info(),
+    transfer_ix,
+    signer.as_slice()
+  );
+  transfer(cpi_ctx, amount)?;
+
+  let close_ix = CloseAccount {
+    account: application_token_account.to_account_info(),
+    destination: ctx.accounts.offeree.to_account_info(),
+    authority: application.to_account_info(),
+  };
+  let cpi_ctx = CpiContext::new_with_signer(
+    ctx.accounts.token_program.to_account_info(),
+    close_ix,
+    signer.as_slice(),
+  );
+  anchor_spl::token::close_account(cpi_ctx)?;
+
+  emit_cpi!(ApplicationWithdrawn {
+    quest,
+    actor: Some(offeree),
+    amount,
+    slot: Clock::get()?.slot,
+  });
+
+  Ok(())
+}
@@ -0,0 +1,103 @@
+// releases a slice of the unstaked tokens every unstaked_vote_unlock_interval until depletion
+
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
+
+use crate::{
+  state::Config,
+  state::ConfigError,
+  state::VotePower,
+  state::VotePowerError,
+};
+
+#[derive(Accounts)]
+pub struct WithdrawStakedVotes<'info> {
+
+  #[account(
+    mut,
+    seeds = [
+      b"vote_power",
+      owner.key().as_ref(),
+    ],
+    bump = vote_power.bump,
+    has_one = owner,
+    constraint = vote_power.unstake_timestamp.is_some() @ VotePowerError::NoUnstakeInProgress,
+  )]
+  pub vote_power: Box<Account<'info, VotePower>>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = vote_power,
+  )]
+  pub vault_token_account: Account<'info, TokenAccount>,
+
+  #[account(
+    mut,
+    associated_token::mint = token_mint,
+    associated_token::authority = owner,
+  )]
+  pub owner_token_account: Account<'info, TokenAccount>,
+
+  pub token_mint: Account<'info, Mint>,
+
+  #[account(
+    seeds = [
+      b"config"
+    ],
+    bump = config.bump,
+    has_one = token_mint,
+    constraint = !config.exits_paused() @ ConfigError::ProgramPaused,
+  )]
+  pub config: Box<Account<'info, Config>>,
+
+  pub owner: Signer<'info>,
+
+  pub system_program: Program<'info, System>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_program: Program<'info, Token>,
+}
+
+pub fn withdraw_staked_votes_handler(ctx: Context<WithdrawStakedVotes>) -> Result<()> {
+
+  let vote_power = &mut ctx.accounts.vote_power;
+  let owner = ctx.accounts.owner.key();
+  let vote_power_bump = vote_power.bump.to_le_bytes();
+
+  let amount = vote_power.claimable(
+    Clock::get()?.slot,
+    ctx.accounts.config.unstaked_vote_unlock_interval,
+  );
+
+  if amount == 0 {
+    return Err(VotePowerError::NothingToWithdraw.into());
+  }
+
+  let seeds = vec![
+    b"vote_power".as_ref(),
+    owner.as_ref(),
+    vote_power_bump.as_ref()
+  ];
+  let signer = vec![seeds.as_slice()];
+
+  let transfer_ix = Transfer {
+    from: ctx.accounts.vault_token_account.to_account_info(),
+    to: ctx.accounts.owner_token_account.to_account_info(),
+    authority: vote_power.to_account_info(),
+  };
+  let cpi_program = ctx.accounts.token_program.to_account_info();
+  let cpi_ctx = CpiContext::new_with_signer(cpi_program, transfer_ix, signer.as_slice());
+  transfer(cpi_ctx, amount)?;
+
+  vote_power.withdrawn += amount;
+
+  // fully released, the wallet can stake again
+  if vote_power.withdrawn == vote_power.unstaked {
+    vote_power.unstaked = 0;
+    vote_power.withdrawn = 0;
+    vote_power.unstake_timestamp = None;
+  }
+
+  Ok(())
+}
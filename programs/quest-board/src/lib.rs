@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 
+pub mod events;
 pub mod instructions;
 pub mod state;
 
@@ -15,6 +16,22 @@ pub mod quest_board {
     initialize_handler(ctx, params)
   }
 
+  pub fn update_config(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+    update_config_handler(ctx, params)
+  }
+
+  pub fn propose_authority(ctx: Context<ProposeAuthority>, params: ProposeAuthorityParams) -> Result<()> {
+    propose_authority_handler(ctx, params)
+  }
+
+  pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+    accept_authority_handler(ctx)
+  }
+
+  pub fn set_pause(ctx: Context<SetPause>, params: SetPauseParams) -> Result<()> {
+    set_pause_handler(ctx, params)
+  }
+
   pub fn create_quest(ctx: Context<CreateQuest>, params: CreateQuestParams) -> Result<()> {
     create_quest_handler(ctx, params)
   }
@@ -31,6 +48,10 @@ pub mod quest_board {
     update_quest_handler(ctx, params)
   }
 
+  pub fn migrate_quest(ctx: Context<MigrateQuest>) -> Result<()> {
+    migrate_quest_handler(ctx)
+  }
+
   pub fn close_quest(ctx: Context<CloseQuest>) -> Result<()> {
     close_quest_handler(ctx)
   }
@@ -39,8 +60,80 @@ pub mod quest_board {
     accept_quest_handler(ctx, params)
   }
 
+  pub fn accept_signed_offer(ctx: Context<AcceptSignedOffer>, params: AcceptSignedOfferParams) -> Result<()> {
+    accept_signed_offer_handler(ctx, params)
+  }
+
+  pub fn apply_to_quest(ctx: Context<ApplyToQuest>, params: ApplyToQuestParams) -> Result<()> {
+    apply_to_quest_handler(ctx, params)
+  }
+
+  pub fn approve_application(ctx: Context<ApproveApplication>) -> Result<()> {
+    approve_application_handler(ctx)
+  }
+
+  pub fn withdraw_application(ctx: Context<WithdrawApplication>) -> Result<()> {
+    withdraw_application_handler(ctx)
+  }
+
+  pub fn complete_milestone(ctx: Context<CompleteMilestone>) -> Result<()> {
+    complete_milestone_handler(ctx)
+  }
+
   pub fn complete_quest(ctx: Context<CompleteQuest>) -> Result<()> {
     complete_quest_handler(ctx)
   }
 
+  pub fn cancel_quest(ctx: Context<CancelQuest>) -> Result<()> {
+    cancel_quest_handler(ctx)
+  }
+
+  pub fn abandon_quest(ctx: Context<AbandonQuest>) -> Result<()> {
+    abandon_quest_handler(ctx)
+  }
+
+  pub fn expire_quest(ctx: Context<ExpireQuest>) -> Result<()> {
+    expire_quest_handler(ctx)
+  }
+
+  pub fn submit_deliverable(ctx: Context<SubmitDeliverable>, params: SubmitDeliverableParams) -> Result<()> {
+    submit_deliverable_handler(ctx, params)
+  }
+
+  pub fn claim_completion(ctx: Context<ClaimCompletion>) -> Result<()> {
+    claim_completion_handler(ctx)
+  }
+
+  pub fn leave_review(ctx: Context<LeaveReview>, params: LeaveReviewParams) -> Result<()> {
+    leave_review_handler(ctx, params)
+  }
+
+  pub fn raise_dispute(ctx: Context<RaiseDispute>) -> Result<()> {
+    raise_dispute_handler(ctx)
+  }
+
+  pub fn stake_vote_power(ctx: Context<StakeVotePower>, params: StakeVotePowerParams) -> Result<()> {
+    stake_vote_power_handler(ctx, params)
+  }
+
+  pub fn unstake_vote_power(ctx: Context<UnstakeVotePower>) -> Result<()> {
+    unstake_vote_power_handler(ctx)
+  }
+
+  pub fn withdraw_staked_votes(ctx: Context<WithdrawStakedVotes>) -> Result<()> {
+    withdraw_staked_votes_handler(ctx)
+  }
+
+  pub fn vote_on_dispute(ctx: Context<VoteOnDispute>, params: VoteOnDisputeParams) -> Result<()> {
+    vote_on_dispute_handler(ctx, params)
+  }
+
+  pub fn resolve_dispute(ctx: Context<ResolveDispute>) -> Result<()> {
+    resolve_dispute_handler(ctx)
+  }
+
+  pub fn settle_dispute(ctx: Context<SettleDispute>) -> Result<()> {
+    settle_dispute_handler(ctx)
+  }
+
 }
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Application {
+  /// Bump nonce of the PDA. (1)
+  pub bump: u8,
+
+  /// The Quest being applied to. (32)
+  pub quest: Pubkey,
+
+  /// The applicant, becomes the offeree once approved. (32)
+  pub offeree: Pubkey,
+
+  /// The amount of governance token staked by the applicant. (8)
+  pub staked: u64,
+
+  /// The hash of the proposal made by the applicant. (32)
+  pub proposal_hash: [u8; 32],
+
+  /// The slot when the application was made. (8)
+  pub timestamp: u64,
+}
+
+impl Application {
+  pub fn len() -> usize {
+    8 + 1 + 32 + 32 + 8 + 32 + 8
+  }
+}
@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{allocate, assign, transfer, Allocate, Assign, Transfer};
+use anchor_spl::associated_token::{create, Create};
+use anchor_spl::token_2022::{initialize_mint2, mint_to, InitializeMint2, MintTo};
+use anchor_spl::token_2022::spl_token_2022::{extension::ExtensionType, state::Mint};
+use anchor_spl::token_2022_extensions::{non_transferable_mint_initialize, NonTransferableMintInitialize};
+
+use crate::state::Quest;
+
+#[account]
+pub struct Badge {
+  /// Bump nonce of the PDA. (1)
+  pub bump: u8,
+
+  /// The Quest account the badge was earned on. (32)
+  pub quest: Pubkey,
+
+  /// The id of the Quest. (32)
+  pub quest_id: Pubkey,
+
+  /// The hash of the Quest details. (32)
+  pub details_hash: [u8; 32],
+
+  /// The offeree who earned the badge. (32)
+  pub recipient: Pubkey,
+
+  /// The non-transferable Token-2022 mint of the badge. (32)
+  pub mint: Pubkey,
+
+  /// The slot when the badge was minted. (8)
+  pub timestamp: u64,
+}
+
+impl Badge {
+  pub fn len() -> usize {
+    8 + 1 + 32 + 32 + 32 + 32 + 32 + 8
+  }
+}
+
+/// Accounts needed to mint a badge, the mint being the PDA [b"badge_mint", quest].
+/// The optional ones are only passed when the badge is asked for.
+pub struct BadgeMint<'info> {
+  pub payer: Option<AccountInfo<'info>>,
+  pub recipient: AccountInfo<'info>,
+  pub recipient_token_account: Option<AccountInfo<'info>>,
+  pub mint: Option<AccountInfo<'info>>,
+  pub config: AccountInfo<'info>,
+  pub token_program: Option<AccountInfo<'info>>,
+  pub associated_token_program: AccountInfo<'info>,
+  pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> BadgeMint<'info> {
+  /// Creates the non-transferable mint of the badge, with the Config PDA as mint authority,
+  /// mints the single badge to the recipient's associated token account and records it in `badge`.
+  pub fn mint(
+    &self,
+    badge: &mut Badge,
+    badge_bump: u8,
+    quest: &Account<'info, Quest>,
+    mint_bump: u8,
+    config_bump: u8,
+  ) -> Result<()> {
+    let (payer, recipient_token_account, mint, token_program) = match (
+      &self.payer,
+      &self.recipient_token_account,
+      &self.mint,
+      &self.token_program,
+    ) {
+      (Some(payer), Some(recipient_token_account), Some(mint), Some(token_program)) =>
+        (payer, recipient_token_account, mint, token_program),
+      _ => return Err(BadgeError::BadgeAccountsMissing.into()),
+    };
+
+    let quest_key = quest.key();
+    let mint_bump = mint_bump.to_le_bytes();
+    let mint_seeds = vec![
+      b"badge_mint".as_ref(),
+      quest_key.as_ref(),
+      mint_bump.as_ref()
+    ];
+    let mint_signer = vec![mint_seeds.as_slice()];
+
+    let config_bump = config_bump.to_le_bytes();
+    let config_seeds = vec![
+      b"config".as_ref(),
+      config_bump.as_ref()
+    ];
+    let config_signer = vec![config_seeds.as_slice()];
+
+    // fund, allocate and assign rather than create_account, which fails if anyone sent lamports to the address
+    let space = ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::NonTransferable])?;
+    let lamports = Rent::get()?.minimum_balance(space).saturating_sub(mint.lamports());
+
+    if lamports > 0 {
+      let cpi_ctx = CpiContext::new(
+        self.system_program.clone(),
+        Transfer {
+          from: payer.clone(),
+          to: mint.clone(),
+        },
+      );
+      transfer(cpi_ctx, lamports)?;
+    }
+
+    let cpi_ctx = CpiContext::new_with_signer(
+      self.system_program.clone(),
+      Allocate {
+        account_to_allocate: mint.clone(),
+      },
+      mint_signer.as_slice(),
+    );
+    allocate(cpi_ctx, space as u64)?;
+
+    let cpi_ctx = CpiContext::new_with_signer(
+      self.system_program.clone(),
+      Assign {
+        account_to_assign: mint.clone(),
+      },
+      mint_signer.as_slice(),
+    );
+    assign(cpi_ctx, token_program.key)?;
+
+    // the extension goes before the mint initialization
+    let cpi_ctx = CpiContext::new(
+      token_program.clone(),
+      NonTransferableMintInitialize {
+        token_program_id: token_program.clone(),
+        mint: mint.clone(),
+      },
+    );
+    non_transferable_mint_initialize(cpi_ctx)?;
+
+    let cpi_ctx = CpiContext::new(
+      token_program.clone(),
+      InitializeMint2 {
+        mint: mint.clone(),
+      },
+    );
+    initialize_mint2(cpi_ctx, 0, self.config.key, None)?;
+
+    let cpi_ctx = CpiContext::new(
+      self.associated_token_program.clone(),
+      Create {
+        payer: payer.clone(),
+        associated_token: recipient_token_account.clone(),
+        authority: self.recipient.clone(),
+        mint: mint.clone(),
+        system_program: self.system_program.clone(),
+        token_program: token_program.clone(),
+      },
+    );
+    create(cpi_ctx)?;
+
+    let cpi_ctx = CpiContext::new_with_signer(
+      token_program.clone(),
+      MintTo {
+        mint: mint.clone(),
+        to: recipient_token_account.clone(),
+        authority: self.config.clone(),
+      },
+      config_signer.as_slice(),
+    );
+    mint_to(cpi_ctx, 1)?;
+
+    badge.bump = badge_bump;
+    badge.quest = quest_key;
+    badge.quest_id = quest.id;
+    badge.details_hash = quest.details_hash;
+    badge.recipient = self.recipient.key();
+    badge.mint = mint.key();
+    badge.timestamp = Clock::get()?.slot;
+
+    Ok(())
+  }
+}
+
+#[error_code]
+pub enum BadgeError {
+  #[msg("The badge accounts are missing.")]
+  BadgeAccountsMissing,
+
+  #[msg("Only the offeree winning the dispute earns a badge.")]
+  BadgeNotEarned,
+}
@@ -2,6 +2,19 @@
 
 use anchor_lang::prelude::*;
 
+/// Block time is around 400ms, so a day is around 216000 slots.
+pub const SLOTS_PER_DAY: u64 = 216_000;
+
+/// Pauses the quest, dispute and staking instructions.
+pub const PAUSE_QUESTS: u8 = 1 << 0;
+
+/// Pauses the withdrawal-style exits (close_quest, settle_dispute, cancel_quest, abandon_quest,
+/// withdraw_application, unstake_vote_power, withdraw_staked_votes).
+pub const PAUSE_EXITS: u8 = 1 << 1;
+
+/// The abandon penalty is in basis points, at most the whole stake.
+pub const MAX_ABANDON_PENALTY: u16 = 10_000;
+
 #[account]
 pub struct Config {
   /// Bump nonce of the PDA. (1)
@@ -37,13 +50,42 @@ pub struct Config {
   /// Interval in slots to unlock a portion of the staked votes until depletion. (8)
   pub unstaked_vote_unlock_interval: u64,
 
-  /// Unused reserved byte space for future additive changes. (128)
-  pub _reserved: [u8; 128],
+  /// Slots given to the owner to review a deliverable before the offeree can claim completion. (8)
+  pub deliverable_review_period: u64,
+
+  /// The authority proposed to take over, effective once it accepts. (1 + 32)
+  pub pending_authority: Option<Pubkey>,
+
+  /// Emergency pause flags, see PAUSE_QUESTS and PAUSE_EXITS. (1)
+  pub paused: u8,
+
+  /// SPL token accepted for Quest rewards, besides SOL. (1 + 32)
+  pub reward_mint: Option<Pubkey>,
+
+  /// Basis points of the offeree's stake forfeited when abandoning a Quest. (2)
+  pub abandon_penalty: u16,
+
+  /// Number of slots after completion during which the parties can review each other. (8)
+  pub review_period: u64,
+
+  /// Whether the abandon penalty goes to the treasury rather than the owner. (1)
+  pub abandon_penalty_to_treasury: bool,
+
+  /// Unused reserved byte space for future additive changes. (42)
+  pub _reserved: [u8; 42],
 }
 
 impl Config {
   pub fn len() -> usize {
-    8 + 1 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 128
+    8 + 1 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + (1 + 32) + 1 + (1 + 32) + 2 + 8 + 1 + 42
+  }
+
+  pub fn is_paused(&self) -> bool {
+    self.paused & PAUSE_QUESTS != 0
+  }
+
+  pub fn exits_paused(&self) -> bool {
+    self.paused & PAUSE_EXITS != 0
   }
 }
 
@@ -55,4 +97,25 @@ pub enum ConfigError {
 
   #[msg("The update authority provided is invalid.")]
   InvalidUpdateAuthority,
+
+  #[msg("The treasury provided is invalid.")]
+  InvalidTreasury,
+
+  #[msg("The dispute duration should be greater than zero.")]
+  InvalidDisputeDuration,
+
+  #[msg("The signer is not the pending authority.")]
+  InvalidPendingAuthority,
+
+  #[msg("The pause flags provided are invalid.")]
+  InvalidPauseFlags,
+
+  #[msg("The program is paused.")]
+  ProgramPaused,
+
+  #[msg("The reward mint cannot be the governance token.")]
+  InvalidRewardMint,
+
+  #[msg("The abandon penalty cannot exceed 10000 basis points.")]
+  InvalidAbandonPenalty,
 }
\ No newline at end of file
@@ -1,7 +1,21 @@
+mod application;
+mod badge;
 mod config;
 mod counter;
 mod quest;
+mod quest_status;
+mod quest_vote;
+mod review;
+mod user_profile;
+mod vote_power;
 
+pub use application::*;
+pub use badge::*;
 pub use config::*;
 pub use counter::*;
-pub use quest::*;
\ No newline at end of file
+pub use quest::*;
+pub use quest_status::*;
+pub use quest_vote::*;
+pub use review::*;
+pub use user_profile::*;
+pub use vote_power::*;
\ No newline at end of file
@@ -1,16 +1,42 @@
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_spl::token::{Transfer, transfer, CloseAccount, TokenAccount};
 
-#[account]
+use crate::state::{can_transition, Config, QuestStatus, SLOTS_PER_DAY};
+
+/// Maximum number of milestones of a Quest.
+pub const MAX_MILESTONES: usize = 5;
+
+/// Layout version of the Quest accounts. Legacy accounts are shorter than `Quest::len()` and read as
+/// version 0, the owner's instructions grow them to the current layout, the others need `migrate_quest` first.
+pub const QUEST_VERSION: u8 = 1;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Milestone {
+  /// The slice of the reward released when the milestone is complete. (8)
+  pub reward: u64,
+
+  /// The hash of the milestone details. (32)
+  pub details_hash: [u8; 32],
+
+  /// Whether both parties marked the milestone complete. (1)
+  pub completed: bool,
+}
+
+impl Milestone {
+  pub fn len() -> usize {
+    8 + 32 + 1
+  }
+}
+
+// not `#[account]`, which rejects an account shorter than the struct,
+// the account traits are implemented below to also decode the legacy layout
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct Quest {
   /// Bump nonce of the PDA. (1)
   pub bump: u8,
 
-  /// unpublished: 0, 
-  /// open: 1, 
-  /// taken: 3, 
-  /// complete: 7,
-  /// dispute: 9,
-  /// dispute resolved: 13 (1101: owner won), 14 (1110: offeree won), 15 (1111: draw). (1)
+  /// The byte value of the QuestStatus, changed through `transition`. (1)
   pub status: u8,
 
   /// The owner of the Quest. (32)
@@ -54,21 +80,297 @@ pub struct Quest {
 
   /// Votes for abstained (resulting to draw). (1 + 8)
   pub abstained_votes: Option<u64>,
+
+  /// The party (owner or offeree) who raised the dispute. (1 + 32)
+  pub dispute_raised_by: Option<Pubkey>,
+
+  /// The slot when the dispute was raised. (1 + 8)
+  pub dispute_timestamp: Option<u64>,
+
+  /// The slot after which an open Quest can be expired. (1 + 8)
+  pub accept_deadline: Option<u64>,
+
+  /// The slot after which a taken Quest can be expired. (1 + 8)
+  pub delivery_deadline: Option<u64>,
+
+  /// The hash of the deliverable submitted by the offeree. (1 + 32)
+  pub deliverable_hash: Option<[u8; 32]>,
+
+  /// The slot when the deliverable was submitted, starts the owner's review period. (1 + 8)
+  pub deliverable_timestamp: Option<u64>,
+
+  /// The reward escrowed for the offeree, in lamports or in `reward_mint` tokens. (8)
+  pub reward: u64,
+
+  /// The SPL token of the reward, SOL if none. (1 + 32)
+  pub reward_mint: Option<Pubkey>,
+
+  /// Staged payouts splitting the reward, empty for a single payout on completion. (4 + 5 * 41)
+  pub milestones: Vec<Milestone>,
+
+  /// Index of the milestone in progress, the only one that can be disputed. (1)
+  pub current_milestone: u8,
+
+  /// The reputation an offeree needs to take the Quest, anyone if none. (1 + 8)
+  pub min_reputation: Option<i64>,
+
+  /// The slot when the Quest was completed, starts the review period. (1 + 8)
+  pub completed_timestamp: Option<u64>,
+
+  /// The layout version of the account, bumped by `migrate_quest`. (1)
+  pub version: u8,
+
+  /// Bumped on every acceptance, a signed offer is only valid for the nonce it was signed for. (8)
+  pub offer_nonce: u64,
+
+  /// Room for new fields without another realloc. (24)
+  pub _reserved: [u8; 24],
 }
 
 impl Quest {
   pub fn len() -> usize {
-    8 + 1 + 1 + 32 + 8 + 8 + 8 + 8 + 32 + 32 + (1 + 8) + (1 + 32) + (1 + 8) + (1 + 32) + (1 + 8) + (1 + 8) + (1 + 8)
+    8 + 1 + 1 + 32 + 8 + 8 + 8 + 8 + 32 + 32 + (1 + 8) + (1 + 32) + (1 + 8) + (1 + 32) + (1 + 8) + (1 + 8) + (1 + 8) + (1 + 32) + (1 + 8) + (1 + 8) + (1 + 8) + (1 + 32) + (1 + 8) + 8 + (1 + 32) + (4 + MAX_MILESTONES * Milestone::len()) + 1 + (1 + 8) + (1 + 8) + 1 + 8 + 24
   }
 
-  pub fn close_account(&self) -> Result<()> {
+  /// Whether the account has the current layout, a legacy one is grown when the owner writes it.
+  pub fn is_migrated(&self) -> bool {
+    self.version >= QUEST_VERSION
+  }
 
-    // todo: calculate placement decay fee, for now, return 100% of the placement fee
-    // todo: possibly pass config (treasury) and owner
+  pub fn is(&self, status: QuestStatus) -> bool {
+    self.status == status as u8
+  }
 
+  /// Moves the Quest to the `to` status, if `can_transition` allows it.
+  pub fn transition(&mut self, to: QuestStatus) -> Result<()> {
+    if !can_transition(QuestStatus::try_from(self.status)?, to) {
+      return Err(QuestError::InvalidStatusTransition.into());
+    }
+
+    self.status = to as u8;
     Ok(())
   }
 
+  /// The reward not released by completed milestones yet.
+  pub fn remaining_reward(&self) -> u64 {
+    let released: u64 = self.milestones.iter()
+      .filter(|milestone| milestone.completed)
+      .map(|milestone| milestone.reward)
+      .sum();
+
+    self.reward - released
+  }
+
+  /// The reward at stake in a dispute, only the current milestone if the Quest has any.
+  pub fn disputed_reward(&self) -> u64 {
+    if self.milestones.is_empty() {
+      return self.reward;
+    }
+
+    self.milestones.get(self.current_milestone as usize).map_or(0, |milestone| milestone.reward)
+  }
+
+  /// Outcome of the dispute against the vote threshold: owner won, offeree won or draw.
+  /// Falling short of the threshold, or no clear majority, results to a draw.
+  pub fn dispute_outcome(&self, vote_threshold: u64) -> QuestStatus {
+    let owner_votes = self.owner_votes.unwrap_or(0);
+    let offeree_votes = self.offeree_votes.unwrap_or(0);
+    let abstained_votes = self.abstained_votes.unwrap_or(0);
+
+    if owner_votes.saturating_add(offeree_votes).saturating_add(abstained_votes) < vote_threshold {
+      return QuestStatus::Draw;
+    }
+
+    if owner_votes > offeree_votes && owner_votes > abstained_votes {
+      QuestStatus::OwnerWon
+    } else if offeree_votes > owner_votes && offeree_votes > abstained_votes {
+      QuestStatus::OffereeWon
+    } else {
+      QuestStatus::Draw
+    }
+  }
+
+  /// Placement decay fee, `decay_fee` for every day (or part of it) beyond `decay_start`
+  /// from the creation of the Quest to the `slot` it is closed, capped at `placement_paid`.
+  pub fn decay_fee(&self, slot: u64, config: &Config) -> u64 {
+    let lingered = slot.saturating_sub(self.timestamp);
+
+    if lingered <= config.decay_start {
+      return 0;
+    }
+
+    let days = (lingered - config.decay_start).div_ceil(SLOTS_PER_DAY);
+
+    config.decay_fee.saturating_mul(days).min(self.placement_paid)
+  }
+
+  /// Sends the placement decay fee to the treasury, the rest of the placement fee
+  /// returns to the owner together with the rent when the account is closed. Returns the fee.
+  pub fn close_account<'info>(
+    &self,
+    quest: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    config: &Config,
+  ) -> Result<u64> {
+    let fee = self.decay_fee(Clock::get()?.slot, config);
+
+    if fee > 0 {
+      **quest.try_borrow_mut_lamports()? -= fee;
+      **treasury.try_borrow_mut_lamports()? += fee;
+    }
+
+    Ok(fee)
+  }
+
+  /// Releases `amount` of the reward, straight from the lamports of the Quest account for SOL rewards,
+  /// or from the reward escrow to the recipient's token account for SPL rewards.
+  pub fn release_reward<'info>(
+    &self,
+    quest: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+    recipient_token_account: Option<AccountInfo<'info>>,
+    reward_escrow: Option<AccountInfo<'info>>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+  ) -> Result<()> {
+    if amount == 0 {
+      return Ok(());
+    }
+
+    if self.reward_mint.is_none() {
+      **quest.try_borrow_mut_lamports()? -= amount;
+      **recipient.try_borrow_mut_lamports()? += amount;
+      return Ok(());
+    }
+
+    let (reward_escrow, recipient_token_account) = match (reward_escrow, recipient_token_account) {
+      (Some(reward_escrow), Some(recipient_token_account)) => (reward_escrow, recipient_token_account),
+      _ => return Err(QuestError::RewardAccountsMissing.into()),
+    };
+
+    let quest_bump = self.bump.to_le_bytes();
+    let seeds = vec![
+      b"quest".as_ref(),
+      self.id.as_ref(),
+      quest_bump.as_ref()
+    ];
+    let signer = vec![seeds.as_slice()];
+
+    let transfer_ix = Transfer {
+      from: reward_escrow,
+      to: recipient_token_account,
+      authority: quest.clone(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.clone(), transfer_ix, signer.as_slice());
+    transfer(cpi_ctx, amount)
+  }
+
+  /// Refunds what is left of the reward to the owner and closes its escrow,
+  /// a SOL reward returns with the rent when the Quest account is closed.
+  pub fn refund_reward<'info>(
+    &self,
+    quest: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    owner_token_account: Option<AccountInfo<'info>>,
+    reward_escrow: Option<AccountInfo<'info>>,
+    token_program: &AccountInfo<'info>,
+  ) -> Result<()> {
+    if let Some(reward_escrow) = &reward_escrow {
+      // read the balance left by the transfers earlier in the instruction
+      let amount = TokenAccount::try_deserialize(&mut &reward_escrow.try_borrow_data()?[..])?.amount;
+      self.release_reward(quest, owner, owner_token_account, Some(reward_escrow.clone()), token_program, amount)?;
+    }
+
+    self.close_reward_escrow(quest, reward_escrow, owner, token_program)
+  }
+
+  /// Closes the (drained) reward escrow of an SPL reward, nothing to do for SOL rewards.
+  pub fn close_reward_escrow<'info>(
+    &self,
+    quest: &AccountInfo<'info>,
+    reward_escrow: Option<AccountInfo<'info>>,
+    destination: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+  ) -> Result<()> {
+    if self.reward_mint.is_none() {
+      return Ok(());
+    }
+
+    let reward_escrow = reward_escrow.ok_or(QuestError::RewardAccountsMissing)?;
+
+    let quest_bump = self.bump.to_le_bytes();
+    let seeds = vec![
+      b"quest".as_ref(),
+      self.id.as_ref(),
+      quest_bump.as_ref()
+    ];
+    let signer = vec![seeds.as_slice()];
+
+    let close_ix = CloseAccount {
+      account: reward_escrow,
+      destination: destination.clone(),
+      authority: quest.clone(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.clone(), close_ix, signer.as_slice());
+    anchor_spl::token::close_account(cpi_ctx)
+  }
+
+}
+
+impl Discriminator for Quest {
+  /// sha256("account:Quest")[..8], as `#[account]` would derive it.
+  const DISCRIMINATOR: [u8; 8] = [0x44, 0x4e, 0x33, 0x17, 0xcc, 0x1b, 0x4c, 0x84];
+}
+
+impl Owner for Quest {
+  fn owner() -> Pubkey {
+    crate::ID
+  }
+}
+
+impl AccountSerialize for Quest {
+  /// Always writes the current layout, a legacy Quest is only written once the owner grew it with `realloc`.
+  fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
+    if self.version < QUEST_VERSION {
+      let mut quest = self.clone();
+      quest.version = QUEST_VERSION;
+      return quest.try_serialize(writer);
+    }
+
+    if writer.write_all(&Quest::DISCRIMINATOR).is_err() {
+      return Err(ErrorCode::AccountDidNotSerialize.into());
+    }
+
+    AnchorSerialize::serialize(self, writer).map_err(|_| ErrorCode::AccountDidNotSerialize.into())
+  }
+}
+
+impl AccountDeserialize for Quest {
+  fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+    if buf.len() < Quest::DISCRIMINATOR.len() {
+      return Err(ErrorCode::AccountDiscriminatorNotFound.into());
+    }
+
+    if buf[..8] != Quest::DISCRIMINATOR {
+      return Err(Error::from(ErrorCode::AccountDiscriminatorMismatch).with_account_name("Quest"));
+    }
+
+    Quest::try_deserialize_unchecked(buf)
+  }
+
+  /// Decodes either layout. The legacy layout lacks the trailing fields, which read as zeros:
+  /// none for the options, an empty `milestones` and version 0.
+  fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+    let mut data: &[u8] = &buf[8..];
+    let padded;
+
+    if data.len() < Quest::len() - 8 {
+      padded = [data, &vec![0; Quest::len() - 8 - data.len()]].concat();
+      data = &padded;
+    }
+
+    AnchorDeserialize::deserialize(&mut data).map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+  }
 }
 
 #[error_code]
@@ -102,4 +404,320 @@ pub enum QuestError {
 
   #[msg("The Quest cannot be completed.")]
   CompleteNotAllowed,
-}
\ No newline at end of file
+
+  #[msg("A dispute can only be raised on a taken Quest.")]
+  DisputeNotAllowed,
+
+  #[msg("Only the owner or the offeree can raise a dispute.")]
+  NotQuestParticipant,
+
+  #[msg("The Quest is not in dispute.")]
+  ResolveNotAllowed,
+
+  #[msg("The dispute period has not ended yet.")]
+  DisputeStillActive,
+
+  #[msg("Only a resolved Quest can be settled.")]
+  SettleNotAllowed,
+
+  #[msg("The deadline should be in the future, and the delivery deadline after the acceptance deadline.")]
+  InvalidDeadline,
+
+  #[msg("The Quest has expired.")]
+  QuestExpired,
+
+  #[msg("The Quest cannot be expired.")]
+  ExpireNotAllowed,
+
+  #[msg("Only the offeree of the Quest can do this.")]
+  NotQuestOfferee,
+
+  #[msg("No deliverable has been submitted.")]
+  NoDeliverable,
+
+  #[msg("The owner's review period has not ended yet.")]
+  ReviewPeriodActive,
+
+  #[msg("The offer has expired.")]
+  OfferExpired,
+
+  #[msg("The offer is not signed by the owner of the Quest.")]
+  InvalidOfferSignature,
+
+  #[msg("The reward mint is not accepted.")]
+  InvalidRewardMint,
+
+  #[msg("The reward token accounts are missing.")]
+  RewardAccountsMissing,
+
+  #[msg("Too many milestones.")]
+  TooManyMilestones,
+
+  #[msg("The milestone rewards should add up to the reward.")]
+  InvalidMilestoneRewards,
+
+  #[msg("There is no milestone left to complete.")]
+  MilestoneNotAllowed,
+
+  #[msg("Only a taken Quest can be cancelled.")]
+  CancelNotAllowed,
+
+  #[msg("Only a taken Quest can be abandoned.")]
+  AbandonNotAllowed,
+
+  #[msg("Invalid Quest status.")]
+  InvalidStatus,
+
+  #[msg("The Quest cannot move to this status.")]
+  InvalidStatusTransition,
+
+  #[msg("Only the owner of the Quest can do this.")]
+  NotQuestOwner,
+
+  #[msg("The Quest account already has the current layout.")]
+  AlreadyMigrated,
+
+  #[msg("The treasury token account is missing.")]
+  TreasuryTokenAccountMissing,
+
+  #[msg("The Quest account has the legacy layout, the owner should migrate it first.")]
+  QuestNotMigrated,
+}
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// The Quest account as first deployed, 249 bytes with the discriminator.
+  #[derive(AnchorSerialize)]
+  struct BaselineQuest {
+    bump: u8,
+    status: u8,
+    owner: Pubkey,
+    timestamp: u64,
+    staked: u64,
+    min_stake_required: u64,
+    placement_paid: u64,
+    details_hash: [u8; 32],
+    id: Pubkey,
+    accepted_timestamp: Option<u64>,
+    offeree: Option<Pubkey>,
+    offeree_staked: Option<u64>,
+    offeree_proposal_hash: Option<[u8; 32]>,
+    owner_votes: Option<u64>,
+    offeree_votes: Option<u64>,
+    abstained_votes: Option<u64>,
+  }
+
+  fn baseline_data(quest: &BaselineQuest) -> Vec<u8> {
+    let mut data = Quest::DISCRIMINATOR.to_vec();
+    quest.serialize(&mut data).unwrap();
+    data.resize(249, 0);
+    data
+  }
+
+  fn quest(milestones: usize) -> Quest {
+    Quest {
+      bump: 254,
+      status: QuestStatus::Open as u8,
+      owner: Pubkey::new_unique(),
+      timestamp: 1,
+      staked: 100,
+      min_stake_required: 10,
+      placement_paid: 0,
+      details_hash: [7; 32],
+      id: Pubkey::new_unique(),
+      accepted_timestamp: None,
+      offeree: None,
+      offeree_staked: None,
+      offeree_proposal_hash: None,
+      owner_votes: None,
+      offeree_votes: None,
+      abstained_votes: None,
+      dispute_raised_by: None,
+      dispute_timestamp: None,
+      accept_deadline: Some(2),
+      delivery_deadline: None,
+      deliverable_hash: None,
+      deliverable_timestamp: None,
+      reward: 50,
+      reward_mint: None,
+      milestones: vec![Milestone { reward: 10, details_hash: [1; 32], completed: false }; milestones],
+      current_milestone: 0,
+      min_reputation: Some(-1),
+      completed_timestamp: None,
+      version: QUEST_VERSION,
+      offer_nonce: 0,
+      _reserved: [0; 24],
+    }
+  }
+
+  /// The account data as written before the version byte, in an account of the legacy size.
+  fn legacy_data(quest: &Quest) -> Vec<u8> {
+    let legacy_len = Quest::len() - 1 - 32;
+    let mut data = Vec::new();
+    quest.try_serialize(&mut data).unwrap();
+    data.truncate(data.len() - 1 - 32);
+    data.resize(legacy_len, 0);
+    data
+  }
+
+  #[test]
+  fn decodes_the_current_layout() {
+    let mut data = Vec::new();
+    quest(2).try_serialize(&mut data).unwrap();
+    data.resize(Quest::len(), 0);
+
+    let decoded = Quest::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(decoded.version, QUEST_VERSION);
+    assert_eq!(decoded.milestones.len(), 2);
+  }
+
+  #[test]
+  fn decodes_the_legacy_layout_as_version_0() {
+    for milestones in 0..=MAX_MILESTONES {
+      let quest = quest(milestones);
+      let decoded = Quest::try_deserialize(&mut legacy_data(&quest).as_slice()).unwrap();
+
+      assert_eq!(decoded.version, 0);
+      assert_eq!(decoded.id, quest.id);
+      assert_eq!(decoded.milestones.len(), milestones);
+      assert_eq!(decoded.min_reputation, quest.min_reputation);
+    }
+  }
+
+  #[test]
+  fn rejects_other_accounts() {
+    let mut data = legacy_data(&quest(0));
+    data[0] ^= 1;
+    assert!(Quest::try_deserialize(&mut data.as_slice()).is_err());
+  }
+
+  #[test]
+  fn discriminator_matches_the_account_attribute() {
+    let hash = anchor_lang::solana_program::hash::hash(b"account:Quest");
+    assert_eq!(Quest::DISCRIMINATOR, hash.to_bytes()[..8]);
+  }
+
+  #[test]
+  fn decodes_a_baseline_account() {
+    let offeree = Pubkey::new_unique();
+    let baseline = BaselineQuest {
+      bump: 255,
+      status: QuestStatus::Dispute as u8,
+      owner: Pubkey::new_unique(),
+      timestamp: 1,
+      staked: 100,
+      min_stake_required: 10,
+      placement_paid: 5,
+      details_hash: [3; 32],
+      id: Pubkey::new_unique(),
+      accepted_timestamp: Some(2),
+      offeree: Some(offeree),
+      offeree_staked: Some(10),
+      offeree_proposal_hash: Some([4; 32]),
+      owner_votes: Some(1),
+      offeree_votes: Some(2),
+      abstained_votes: Some(3),
+    };
+    let data = baseline_data(&baseline);
+
+    let quest = Quest::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(quest.version, 0);
+    assert_eq!(quest.id, baseline.id);
+    assert_eq!(quest.offeree, Some(offeree));
+    assert_eq!(quest.abstained_votes, Some(3));
+    assert_eq!(quest.reward, 0);
+    assert_eq!(quest.reward_mint, None);
+    assert!(quest.milestones.is_empty());
+    assert_eq!(quest.offer_nonce, 0);
+  }
+
+  #[test]
+  fn writes_a_legacy_quest_in_the_current_layout() {
+    let mut legacy = quest(1);
+    legacy.version = 0;
+
+    let mut data = Vec::new();
+    legacy.try_serialize(&mut data).unwrap();
+    data.resize(Quest::len(), 0);
+
+    let quest = Quest::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(quest.version, QUEST_VERSION);
+    assert_eq!(quest.milestones.len(), 1);
+  }
+
+  fn config(decay_fee: u64, decay_start: u64) -> Config {
+    Config {
+      bump: 255,
+      authority: Pubkey::new_unique(),
+      treasury: Pubkey::new_unique(),
+      token_mint: Pubkey::new_unique(),
+      base_fee: 0,
+      decay_fee,
+      decay_start,
+      vote_threshold: 0,
+      dispute_duration: 0,
+      staked_vote_power_start: 0,
+      unstaked_vote_unlock_interval: 0,
+      deliverable_review_period: 0,
+      pending_authority: None,
+      paused: 0,
+      reward_mint: None,
+      abandon_penalty: 0,
+      review_period: 0,
+      abandon_penalty_to_treasury: false,
+      _reserved: [0; 42],
+    }
+  }
+
+  #[test]
+  fn decay_fee_starts_after_decay_start() {
+    let mut quest = quest(0);
+    quest.timestamp = 1_000;
+    quest.placement_paid = 1_000;
+    let config = config(10, SLOTS_PER_DAY);
+
+    assert_eq!(quest.decay_fee(1_000, &config), 0);
+    assert_eq!(quest.decay_fee(1_000 + SLOTS_PER_DAY, &config), 0);
+    assert_eq!(quest.decay_fee(1_000 + SLOTS_PER_DAY + 1, &config), 10);
+    assert_eq!(quest.decay_fee(1_000 + 2 * SLOTS_PER_DAY, &config), 10);
+    assert_eq!(quest.decay_fee(1_000 + 3 * SLOTS_PER_DAY + 1, &config), 30);
+  }
+
+  #[test]
+  fn decay_fee_is_capped_at_the_placement_paid() {
+    let mut quest = quest(0);
+    quest.timestamp = 1_000;
+    quest.placement_paid = 25;
+    let config = config(10, 0);
+
+    assert_eq!(quest.decay_fee(1_000 + 2 * SLOTS_PER_DAY, &config), 20);
+    assert_eq!(quest.decay_fee(1_000 + 3 * SLOTS_PER_DAY, &config), 25);
+    assert_eq!(quest.decay_fee(u64::MAX, &config), 25);
+  }
+
+  #[test]
+  fn decay_fee_counts_until_close_regardless_of_acceptance() {
+    let mut quest = quest(0);
+    quest.timestamp = 1_000;
+    quest.placement_paid = 1_000;
+    let config = config(10, 0);
+
+    quest.accepted_timestamp = Some(1_001);
+    assert_eq!(quest.decay_fee(1_000 + 5 * SLOTS_PER_DAY, &config), 50);
+
+    // cancelled or abandoned, then re-listed
+    quest.accepted_timestamp = None;
+    assert_eq!(quest.decay_fee(1_000 + 5 * SLOTS_PER_DAY, &config), 50);
+  }
+
+  #[test]
+  fn dispute_outcome_does_not_overflow() {
+    let mut quest = quest(0);
+    quest.owner_votes = Some(u64::MAX);
+    quest.offeree_votes = Some(u64::MAX - 1);
+    quest.abstained_votes = Some(1);
+
+    assert_eq!(quest.dispute_outcome(u64::MAX), QuestStatus::OwnerWon);
+  }
+}
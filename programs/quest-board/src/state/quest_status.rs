@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+
+use crate::state::QuestError;
+
+/// Status of a Quest, stored in `Quest::status` as its byte value.
+/// The resolved statuses keep their bit patterns: 13 (1101), 14 (1110), 15 (1111).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum QuestStatus {
+  Unpublished = 0,
+  Open = 1,
+  Taken = 3,
+  Complete = 7,
+  Dispute = 9,
+  OwnerWon = 13,
+  OffereeWon = 14,
+  Draw = 15,
+  /// Never stored, the Quest account is closed.
+  Closed = 255,
+}
+
+impl QuestStatus {
+  pub const ALL: [QuestStatus; 9] = [
+    QuestStatus::Unpublished,
+    QuestStatus::Open,
+    QuestStatus::Taken,
+    QuestStatus::Complete,
+    QuestStatus::Dispute,
+    QuestStatus::OwnerWon,
+    QuestStatus::OffereeWon,
+    QuestStatus::Draw,
+    QuestStatus::Closed,
+  ];
+
+  pub fn is_resolved(&self) -> bool {
+    matches!(self, QuestStatus::OwnerWon | QuestStatus::OffereeWon | QuestStatus::Draw)
+  }
+}
+
+impl TryFrom<u8> for QuestStatus {
+  type Error = Error;
+
+  fn try_from(status: u8) -> Result<Self> {
+    QuestStatus::ALL.into_iter()
+      .find(|quest_status| *quest_status as u8 == status)
+      .ok_or_else(|| QuestError::InvalidStatus.into())
+  }
+}
+
+/// The only place the allowed status changes are spelled out, instructions go through `Quest::transition`.
+pub fn can_transition(from: QuestStatus, to: QuestStatus) -> bool {
+  use QuestStatus::*;
+
+  matches!(
+    (from, to),
+    // publish_quest, close_quest
+    (Unpublished, Open) | (Unpublished, Closed) |
+    // unpublish_quest, accept_quest (and its variants), expire_quest
+    (Open, Unpublished) | (Open, Taken) | (Open, Closed) |
+    // abandon_quest, cancel_quest or expire_quest, complete_quest or claim_completion, raise_dispute
+    (Taken, Open) | (Taken, Unpublished) | (Taken, Complete) | (Taken, Dispute) |
+    // close_quest
+    (Complete, Closed) |
+    // resolve_dispute
+    (Dispute, OwnerWon) | (Dispute, OffereeWon) | (Dispute, Draw) |
+    // settle_dispute
+    (OwnerWon, Closed) | (OffereeWon, Closed) | (Draw, Closed)
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use QuestStatus::*;
+
+  const LEGAL: [(QuestStatus, QuestStatus); 16] = [
+    (Unpublished, Open),
+    (Unpublished, Closed),
+    (Open, Unpublished),
+    (Open, Taken),
+    (Open, Closed),
+    (Taken, Open),
+    (Taken, Unpublished),
+    (Taken, Complete),
+    (Taken, Dispute),
+    (Complete, Closed),
+    (Dispute, OwnerWon),
+    (Dispute, OffereeWon),
+    (Dispute, Draw),
+    (OwnerWon, Closed),
+    (OffereeWon, Closed),
+    (Draw, Closed),
+  ];
+
+  #[test]
+  fn legal_transitions_are_allowed() {
+    for (from, to) in LEGAL {
+      assert!(can_transition(from, to), "{:?} -> {:?} should be allowed", from, to);
+    }
+  }
+
+  #[test]
+  fn every_other_transition_is_rejected() {
+    for from in QuestStatus::ALL {
+      for to in QuestStatus::ALL {
+        if !LEGAL.contains(&(from, to)) {
+          assert!(!can_transition(from, to), "{:?} -> {:?} should be rejected", from, to);
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn nothing_leaves_closed() {
+    for to in QuestStatus::ALL {
+      assert!(!can_transition(Closed, to));
+    }
+  }
+
+  #[test]
+  fn byte_values_are_unchanged() {
+    let bytes: Vec<u8> = QuestStatus::ALL.iter().map(|status| *status as u8).collect();
+    assert_eq!(bytes, vec![0, 1, 3, 7, 9, 13, 14, 15, 255]);
+
+    for status in QuestStatus::ALL {
+      assert_eq!(QuestStatus::try_from(status as u8).unwrap(), status);
+    }
+
+    assert!(QuestStatus::try_from(2).is_err());
+  }
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct QuestVote {
+  /// Bump nonce of the PDA. (1)
+  pub bump: u8,
+
+  /// The disputed Quest. (32)
+  pub quest: Pubkey,
+
+  /// The wallet who cast the vote. (32)
+  pub voter: Pubkey,
+
+  /// owner: 0,
+  /// offeree: 1,
+  /// abstain: 2. (1)
+  pub vote: u8,
+
+  /// The vote power (staked governance tokens) used. (8)
+  pub power: u64,
+
+  /// The slot when the vote was cast. (8)
+  pub timestamp: u64,
+}
+
+impl QuestVote {
+  pub fn len() -> usize {
+    8 + 1 + 32 + 32 + 1 + 8 + 8
+  }
+}
+
+#[error_code]
+pub enum QuestVoteError {
+  #[msg("The Quest is not in dispute.")]
+  DisputeNotActive,
+
+  #[msg("The owner and the offeree of the Quest cannot vote.")]
+  PartyCannotVote,
+
+  #[msg("The vote is invalid.")]
+  InvalidVote,
+
+  #[msg("There is no vote power to use.")]
+  NoVotePower,
+
+  #[msg("The vote power is frozen by an unstake request.")]
+  VotePowerFrozen,
+
+  #[msg("The vote power is still warming up.")]
+  VotePowerNotActive,
+
+  #[msg("The voting period of the dispute has ended.")]
+  VotingClosed,
+
+  #[msg("The vote tally overflows.")]
+  VotesOverflow,
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Review {
+  /// Bump nonce of the PDA. (1)
+  pub bump: u8,
+
+  /// The completed Quest being reviewed. (32)
+  pub quest: Pubkey,
+
+  /// The party leaving the review. (32)
+  pub reviewer: Pubkey,
+
+  /// The other party, being reviewed. (32)
+  pub reviewee: Pubkey,
+
+  /// The rating, from 1 to 5. (1)
+  pub rating: u8,
+
+  /// The hash of the review. (32)
+  pub review_hash: [u8; 32],
+
+  /// The slot when the review was left. (8)
+  pub timestamp: u64,
+}
+
+impl Review {
+  pub fn len() -> usize {
+    8 + 1 + 32 + 32 + 32 + 1 + 32 + 8
+  }
+}
+
+#[error_code]
+pub enum ReviewError {
+  #[msg("Only a complete Quest can be reviewed.")]
+  ReviewNotAllowed,
+
+  #[msg("The rating should be from 1 to 5.")]
+  InvalidRating,
+
+  #[msg("The review period is over.")]
+  ReviewPeriodOver,
+
+  #[msg("The profile is not of the other party of the Quest.")]
+  InvalidReviewee,
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct UserProfile {
+  /// Bump nonce of the PDA. (1)
+  pub bump: u8,
+
+  /// The wallet the profile belongs to. (32)
+  pub wallet: Pubkey,
+
+  /// Number of Quests created as owner. (8)
+  pub quests_posted: u64,
+
+  /// Number of Quests taken as offeree. (8)
+  pub quests_taken: u64,
+
+  /// Number of Quests completed, as owner or offeree. (8)
+  pub quests_completed: u64,
+
+  /// Number of Quests abandoned as offeree. (8)
+  pub quests_abandoned: u64,
+
+  /// Number of Quests closed by the owner without being completed. (8)
+  pub quests_closed: u64,
+
+  /// Number of disputes won, as owner or offeree. (8)
+  pub disputes_won: u64,
+
+  /// Number of disputes lost, as owner or offeree. (8)
+  pub disputes_lost: u64,
+
+  /// Sum of the ratings (1 to 5) received from the other party of completed Quests. (8)
+  pub rating_sum: u64,
+
+  /// Number of ratings received. (8)
+  pub rating_count: u64,
+
+  /// Unused reserved byte space for future additive changes. (48)
+  pub _reserved: [u8; 48],
+}
+
+impl UserProfile {
+  pub fn len() -> usize {
+    8 + 1 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 48
+  }
+
+  /// Completed Quests and disputes won, minus abandoned Quests and disputes lost.
+  pub fn reputation(&self) -> i64 {
+    (self.quests_completed + self.disputes_won) as i64 - (self.quests_abandoned + self.disputes_lost) as i64
+  }
+
+  /// Whether the reputation satisfies the minimum required by a Quest, if any.
+  pub fn meets_reputation(&self, min_reputation: Option<i64>) -> bool {
+    match min_reputation {
+      Some(min_reputation) => self.reputation() >= min_reputation,
+      None => true,
+    }
+  }
+}
+
+#[error_code]
+pub enum UserProfileError {
+  #[msg("The reputation is below the minimum required by the Quest.")]
+  ReputationTooLow,
+}
@@ -1,10 +1,154 @@
 use anchor_lang::prelude::*;
 
+/// Number of `unstaked_vote_unlock_interval`s until the unstaked tokens are fully released.
+pub const UNSTAKE_UNLOCK_PERIODS: u64 = 10;
+
 #[account]
 pub struct VotePower {
   /// Bump nonce of the PDA. (1)
   pub bump: u8,
 
-  // todo: unstake slot timestamp, stake slot timestamp, ATA
+  /// The wallet that owns the staked governance tokens. (32)
+  pub owner: Pubkey,
+
+  /// The token vault (ATA owned by this PDA) holding the staked governance tokens. (32)
+  pub vault: Pubkey,
+
+  /// The amount of governance token staked. (8)
+  pub staked: u64,
+
+  /// The slot of the last stake, voting is allowed after `staked_vote_power_start`. (8)
+  pub staked_timestamp: u64,
+
+  /// The amount of governance token being released after an unstake request. (8)
+  pub unstaked: u64,
+
+  /// The amount already withdrawn from `unstaked`. (8)
+  pub withdrawn: u64,
+
+  /// The slot of the unstake request, voting is frozen while this is set. (1 + 8)
+  pub unstake_timestamp: Option<u64>,
+}
+
+impl VotePower {
+  pub fn len() -> usize {
+    8 + 1 + 32 + 32 + 8 + 8 + 8 + 8 + (1 + 8)
+  }
+
+  /// Amount of unstaked tokens that can be withdrawn at the given slot.
+  /// A slice of `unstaked` unlocks every interval until it is depleted.
+  pub fn claimable(&self, slot: u64, interval: u64) -> u64 {
+    let unstake_timestamp = match self.unstake_timestamp {
+      Some(unstake_timestamp) => unstake_timestamp,
+      None => return 0,
+    };
+
+    // a zero interval releases everything at once
+    let periods = slot
+      .saturating_sub(unstake_timestamp)
+      .checked_div(interval)
+      .map_or(UNSTAKE_UNLOCK_PERIODS, |periods| periods.min(UNSTAKE_UNLOCK_PERIODS));
+
+    let unlocked = (self.unstaked as u128 * periods as u128 / UNSTAKE_UNLOCK_PERIODS as u128) as u64;
+
+    unlocked.saturating_sub(self.withdrawn)
+  }
+}
+
+#[error_code]
+pub enum VotePowerError {
+  #[msg("The stake amount should be greater than zero.")]
+  InvalidStakeAmount,
+
+  #[msg("The owner does not have enough governance tokens.")]
+  NotEnoughTokenBalance,
+
+  #[msg("There are no staked governance tokens.")]
+  NothingStaked,
+
+  #[msg("An unstake request is still in progress.")]
+  UnstakeInProgress,
+
+  #[msg("There is no unstake request in progress.")]
+  NoUnstakeInProgress,
+
+  #[msg("There are no unlocked governance tokens to withdraw yet.")]
+  NothingToWithdraw,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const INTERVAL: u64 = 100;
+
+  fn vote_power(unstaked: u64, withdrawn: u64) -> VotePower {
+    VotePower {
+      bump: 255,
+      owner: Pubkey::new_unique(),
+      vault: Pubkey::new_unique(),
+      staked: 0,
+      staked_timestamp: 0,
+      unstaked,
+      withdrawn,
+      unstake_timestamp: Some(1_000),
+    }
+  }
+
+  #[test]
+  fn nothing_is_claimable_without_an_unstake_request() {
+    let mut vote_power = vote_power(1_000, 0);
+    vote_power.unstake_timestamp = None;
+
+    assert_eq!(vote_power.claimable(1_000_000, INTERVAL), 0);
+  }
+
+  #[test]
+  fn a_partial_period_unlocks_nothing_more() {
+    let vote_power = vote_power(1_000, 0);
+
+    assert_eq!(vote_power.claimable(1_000, INTERVAL), 0);
+    assert_eq!(vote_power.claimable(1_000 + INTERVAL - 1, INTERVAL), 0);
+    assert_eq!(vote_power.claimable(1_000 + INTERVAL, INTERVAL), 100);
+    assert_eq!(vote_power.claimable(1_000 + 3 * INTERVAL + 50, INTERVAL), 300);
+  }
+
+  #[test]
+  fn every_period_unlocks_everything() {
+    let vote_power = vote_power(1_000, 0);
+
+    assert_eq!(vote_power.claimable(1_000 + UNSTAKE_UNLOCK_PERIODS * INTERVAL, INTERVAL), 1_000);
+  }
+
+  #[test]
+  fn later_periods_do_not_over_release() {
+    let vote_power = vote_power(1_000, 400);
+
+    assert_eq!(vote_power.claimable(1_000 + (UNSTAKE_UNLOCK_PERIODS + 1) * INTERVAL, INTERVAL), 600);
+    assert_eq!(vote_power.claimable(u64::MAX, INTERVAL), 600);
+  }
+
+  #[test]
+  fn withdrawn_slices_are_deducted() {
+    let vote_power = vote_power(1_000, 300);
+
+    assert_eq!(vote_power.claimable(1_000 + 2 * INTERVAL, INTERVAL), 0);
+    assert_eq!(vote_power.claimable(1_000 + 5 * INTERVAL, INTERVAL), 200);
+  }
+
+  #[test]
+  fn a_zero_interval_unlocks_everything_at_once() {
+    let vote_power = vote_power(1_000, 0);
+
+    assert_eq!(vote_power.claimable(1_000, 0), 1_000);
+  }
+
+  #[test]
+  fn rounding_holds_back_until_the_last_period() {
+    let vote_power = vote_power(7, 0);
 
-}
\ No newline at end of file
+    assert_eq!(vote_power.claimable(1_000 + INTERVAL, INTERVAL), 0);
+    assert_eq!(vote_power.claimable(1_000 + (UNSTAKE_UNLOCK_PERIODS - 1) * INTERVAL, INTERVAL), 6);
+    assert_eq!(vote_power.claimable(1_000 + UNSTAKE_UNLOCK_PERIODS * INTERVAL, INTERVAL), 7);
+  }
+}
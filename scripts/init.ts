@@ -22,6 +22,10 @@ const init = async () => {
     [Buffer.from('counter')],
     program.programId
   )
+  const [programDataPda] = PublicKey.findProgramAddressSync(
+    [program.programId.toBytes()],
+    new PublicKey('BPFLoaderUpgradeab1e11111111111111111111111')
+  )
 
   let config = await program.account.config.fetchNullable(configPda)
 
@@ -45,9 +49,13 @@ const init = async () => {
         disputeDuration: new BN(6480000), // 1 month
         stakedVotePowerStart: new BN(0), // immediate
         unstakedVoteUnlockInterval: new BN(216000), // daily
+        deliverableReviewPeriod: new BN(1512000), // 1 week
+        abandonPenalty: 1000, // 10%
+        reviewPeriod: new BN(3024000), // 2 weeks
       })
       .accounts({
         authority: authority.publicKey,
+        programData: programDataPda,
       })
       .rpc()
 
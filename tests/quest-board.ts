@@ -3,14 +3,17 @@ import {
   BN,
   IdlAccounts,
   setProvider,
+  utils,
   workspace,
 } from '@coral-xyz/anchor'
 import { Program } from '@coral-xyz/anchor'
 import { QuestBoard } from '../target/types/quest_board'
 import {
+  Ed25519Program,
   Keypair,
   LAMPORTS_PER_SOL,
   PublicKey,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
   SystemProgram,
   Transaction,
 } from '@solana/web3.js'
@@ -20,6 +23,7 @@ import {
   getAssociatedTokenAddress,
   getOrCreateAssociatedTokenAccount,
   mintTo,
+  TOKEN_2022_PROGRAM_ID,
 } from '@solana/spl-token'
 
 import { readFileSync } from 'fs'
@@ -37,6 +41,34 @@ function loadKeypair(filePath: string): Keypair {
   return Keypair.fromSecretKey(keypairBuffer)
 }
 
+async function waitForSlot(program: Program<QuestBoard>, slot: number) {
+  while ((await program.provider.connection.getSlot()) <= slot) {
+    await new Promise((r) => setTimeout(r, 400))
+  }
+}
+
+// the events emitted with emit_cpi!, the data of the self-invoked inner instructions after the 8 bytes event tag
+async function cpiEvents(program: Program<QuestBoard>, signature: string) {
+  const tx = await program.provider.connection.getTransaction(signature, {
+    commitment: 'confirmed',
+    maxSupportedTransactionVersion: 0,
+  })
+  const accountKeys = tx.transaction.message.getAccountKeys()
+
+  return tx.meta.innerInstructions
+    .flatMap((inner) => inner.instructions)
+    .filter((ix) =>
+      accountKeys.get(ix.programIdIndex).equals(program.programId)
+    )
+    .map((ix) =>
+      program.coder.events.decode(
+        utils.bytes.base64.encode(
+          Buffer.from(utils.bytes.bs58.decode(ix.data)).subarray(8)
+        )
+      )
+    )
+}
+
 describe('quest-board', () => {
   // Configure the client to use the local cluster.
   setProvider(AnchorProvider.env())
@@ -45,19 +77,29 @@ describe('quest-board', () => {
   const authority = loadKeypair('~/.config/solana/id.json')
   const treasuryKeypair = Keypair.generate()
   const offereeKeypair = Keypair.generate()
+  const voterKeypair = Keypair.generate()
   const questId1 = Keypair.generate()
   const questId2 = Keypair.generate()
+  const questId3 = Keypair.generate()
+  const questId4 = Keypair.generate()
 
   let config: IdlAccounts<QuestBoard>['config']
   let counter: IdlAccounts<QuestBoard>['counter']
   let tokenMint: PublicKey
   let ownerAta: PublicKey
   let offereeAta: PublicKey
+  let voterAta: PublicKey
+
+  const [programDataPda] = PublicKey.findProgramAddressSync(
+    [program.programId.toBytes()],
+    new PublicKey('BPFLoaderUpgradeab1e11111111111111111111111')
+  )
 
-  // const [programDataPda] = PublicKey.findProgramAddressSync(
-  //   [program.programId.toBytes()],
-  //   new PublicKey('BPFLoaderUpgradeab1e11111111111111111111111')
-  // )
+  // ProgramData of an unrelated program, see [[test.genesis]] in Anchor.toml
+  const [otherProgramDataPda] = PublicKey.findProgramAddressSync(
+    [new PublicKey('BaRet75beXts9ddN3i5bKPobLqDhrpaHFsvV8z2c4Ymu').toBytes()],
+    new PublicKey('BPFLoaderUpgradeab1e11111111111111111111111')
+  )
 
   const [configPda] = PublicKey.findProgramAddressSync(
     [Buffer.from('config')],
@@ -79,6 +121,21 @@ describe('quest-board', () => {
     program.programId
   )
 
+  const [questPda3] = PublicKey.findProgramAddressSync(
+    [Buffer.from('quest'), questId3.publicKey.toBytes()],
+    program.programId
+  )
+
+  const [questPda4] = PublicKey.findProgramAddressSync(
+    [Buffer.from('quest'), questId4.publicKey.toBytes()],
+    program.programId
+  )
+
+  const [votePowerPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from('vote_power'), voterKeypair.publicKey.toBytes()],
+    program.programId
+  )
+
   before(async () => {
     const transaction1 = new Transaction().add(
       SystemProgram.transfer({
@@ -90,6 +147,16 @@ describe('quest-board', () => {
 
     await program.provider.sendAndConfirm(transaction1)
 
+    const transaction3 = new Transaction().add(
+      SystemProgram.transfer({
+        fromPubkey: authority.publicKey,
+        toPubkey: voterKeypair.publicKey,
+        lamports: 2 * LAMPORTS_PER_SOL,
+      })
+    )
+
+    await program.provider.sendAndConfirm(transaction3)
+
     // IMPORTANT
     // treasury should have a sufficient rent exempt balance else the quest creation will fail
     const transaction2 = new Transaction().add(
@@ -128,6 +195,15 @@ describe('quest-board', () => {
       )
     ).address
 
+    voterAta = (
+      await getOrCreateAssociatedTokenAccount(
+        program.provider.connection,
+        authority,
+        tokenMint,
+        voterKeypair.publicKey
+      )
+    ).address
+
     await Promise.all([
       mintTo(
         program.provider.connection,
@@ -145,27 +221,143 @@ describe('quest-board', () => {
         authority,
         1000 * 10 ** 9
       ),
+      mintTo(
+        program.provider.connection,
+        authority,
+        tokenMint,
+        voterAta,
+        authority,
+        1000 * 10 ** 9
+      ),
     ])
   })
 
+  const initializeParams = () => ({
+    treasury: treasuryKeypair.publicKey,
+    token: tokenMint,
+    baseFee: new BN(0.0001 * LAMPORTS_PER_SOL),
+    decayFee: new BN(0.0001 * LAMPORTS_PER_SOL),
+    decayStart: new BN(0),
+    voteThreshold: new BN(0),
+    disputeDuration: new BN(20),
+    stakedVotePowerStart: new BN(0),
+    unstakedVoteUnlockInterval: new BN(0),
+    deliverableReviewPeriod: new BN(5),
+    abandonPenalty: 1000,
+    reviewPeriod: new BN(1000),
+  })
+
+  // params of a plain quest, with only what the test needs overridden
+  const questParams = (
+    params: Partial<Parameters<typeof program.methods.createQuest>[0]> = {}
+  ) => ({
+    detailsHash: Array.from(Keypair.generate().publicKey.toBytes()),
+    minStakeRequired: new BN(100 * 10 ** 9),
+    placementPaid: new BN(0),
+    stakeAmount: new BN(100 * 10 ** 9),
+    acceptDeadline: null,
+    deliveryDeadline: null,
+    reward: new BN(0),
+    milestones: [],
+    minReputation: null,
+    ...params,
+  })
+
+  // creates, publishes and accepts a new quest, returning its PDA
+  const createTakenQuest = async (
+    reward = new BN(0),
+    milestones: { reward: BN; detailsHash: number[] }[] = []
+  ) => {
+    const questId = Keypair.generate()
+    const [questPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('quest'), questId.publicKey.toBytes()],
+      program.programId
+    )
+
+    await program.methods
+      .createQuest(questParams({ reward, milestones }))
+      .accounts({
+        owner: authority.publicKey,
+        id: questId.publicKey,
+      })
+      .signers([questId])
+      .rpc()
+
+    await program.methods
+      .publishQuest()
+      .accounts({
+        owner: authority.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+      })
+      .rpc()
+
+    await program.methods
+      .acceptQuest({
+        stakeAmount: new BN(100 * 10 ** 9),
+        offereeProposalHash: Array.from(Keypair.generate().publicKey.toBytes()),
+      })
+      .accounts({
+        offeree: offereeKeypair.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+      })
+      .signers([offereeKeypair])
+      .rpc()
+
+    return questPda
+  }
+
+  it('Cannot be initialized by a wallet other than the upgrade authority', async function () {
+    if (await program.account.config.fetchNullable(configPda)) {
+      this.skip()
+    }
+
+    try {
+      await program.methods
+        .initialize(initializeParams())
+        .accounts({
+          authority: offereeKeypair.publicKey,
+          programData: programDataPda,
+        })
+        .signers([offereeKeypair])
+        .rpc()
+      expect.fail('only the upgrade authority can initialize')
+    } catch (e) {
+      expect(e.error.errorCode.code).to.be.equal('InvalidUpdateAuthority')
+    }
+  })
+
+  it('Cannot be initialized with the program data of another program', async function () {
+    if (await program.account.config.fetchNullable(configPda)) {
+      this.skip()
+    }
+
+    try {
+      await program.methods
+        .initialize(initializeParams())
+        .accounts({
+          authority: authority.publicKey,
+          programData: otherProgramDataPda,
+        })
+        .rpc()
+      expect.fail('the program data should belong to this program')
+    } catch (e) {
+      expect(e.error.errorCode.code).to.be.equal('InvalidProgramData')
+    }
+  })
+
   it('Is initialized!', async () => {
     config = await program.account.config.fetchNullable(configPda)
 
     if (!config) {
       await program.methods
-        .initialize({
-          treasury: treasuryKeypair.publicKey,
-          token: tokenMint,
-          baseFee: new BN(0.0001 * LAMPORTS_PER_SOL),
-          decayFee: new BN(0.0001 * LAMPORTS_PER_SOL),
-          decayStart: new BN(0),
-          voteThreshold: new BN(0),
-          disputeDuration: new BN(0),
-          stakedVotePowerStart: new BN(0),
-          unstakedVoteUnlockInterval: new BN(0),
-        })
+        .initialize(initializeParams())
         .accounts({
           authority: authority.publicKey,
+          programData: programDataPda,
         })
         .rpc()
 
@@ -184,12 +376,7 @@ describe('quest-board', () => {
     console.log('Balance before creation', preBalanceInLamports)
 
     await program.methods
-      .createQuest({
-        detailsHash: Array.from(Keypair.generate().publicKey.toBytes()),
-        minStakeRequired: new BN(100 * 10 ** 9),
-        placementPaid: new BN(0), //new BN(0.1 * LAMPORTS_PER_SOL),
-        stakeAmount: new BN(100 * 10 ** 9),
-      })
+      .createQuest(questParams())
       .accounts({
         owner: authority.publicKey,
         id: questId1.publicKey,
@@ -321,12 +508,7 @@ describe('quest-board', () => {
   it('Can accept the quest', async () => {
     // create a new quest
     await program.methods
-      .createQuest({
-        detailsHash: Array.from(Keypair.generate().publicKey.toBytes()),
-        minStakeRequired: new BN(100 * 10 ** 9),
-        placementPaid: new BN(0), //new BN(0.1 * LAMPORTS_PER_SOL),
-        stakeAmount: new BN(100 * 10 ** 9),
-      })
+      .createQuest(questParams())
       .accounts({
         owner: authority.publicKey,
         id: questId2.publicKey,
@@ -421,4 +603,1443 @@ describe('quest-board', () => {
     const quest = await program.account.quest.fetchNullable(questPda2)
     expect(quest).to.be.equal(null)
   })
+
+  it('Can raise a dispute on a taken quest', async () => {
+    await program.methods
+      .createQuest(questParams())
+      .accounts({
+        owner: authority.publicKey,
+        id: questId3.publicKey,
+      })
+      .signers([questId3])
+      .rpc()
+
+    await program.methods
+      .publishQuest()
+      .accounts({
+        owner: authority.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda3,
+      })
+      .rpc()
+
+    await program.methods
+      .acceptQuest({
+        stakeAmount: new BN(100 * 10 ** 9),
+        offereeProposalHash: Array.from(Keypair.generate().publicKey.toBytes()),
+      })
+      .accounts({
+        offeree: offereeKeypair.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda3,
+      })
+      .signers([offereeKeypair])
+      .rpc()
+
+    await program.methods
+      .raiseDispute()
+      .accounts({
+        signer: offereeKeypair.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda3,
+        owner: authority.publicKey,
+        offeree: offereeKeypair.publicKey,
+      })
+      .signers([offereeKeypair])
+      .rpc()
+
+    const quest = await program.account.quest.fetch(questPda3)
+    expect(quest.status).to.be.equal(9)
+    expect(quest.disputeRaisedBy.equals(offereeKeypair.publicKey)).to.be.true
+    expect(quest.ownerVotes.toNumber()).to.be.equal(0)
+
+    const counter = await program.account.counter.fetch(counterPda)
+    expect(counter.postsTaken.toNumber()).to.be.equal(0)
+    expect(counter.postsInDispute.toNumber()).to.be.equal(1)
+  })
+
+  it('Can stake vote power', async () => {
+    await program.methods
+      .stakeVotePower({
+        amount: new BN(500 * 10 ** 9),
+      })
+      .accounts({
+        owner: voterKeypair.publicKey,
+      })
+      .signers([voterKeypair])
+      .rpc()
+
+    const votePower = await program.account.votePower.fetch(votePowerPda)
+    expect(votePower.owner.equals(voterKeypair.publicKey)).to.be.true
+    expect(votePower.staked.toNumber()).to.be.equal(500 * 10 ** 9)
+
+    const tokenAccountInfo = await getAccount(
+      program.provider.connection,
+      voterAta
+    )
+    expect(Number(tokenAccountInfo.amount) / 10 ** 9).to.be.equal(500)
+  })
+
+  it('Can vote on a dispute', async () => {
+    const [questVotePda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('quest_vote'),
+        questPda3.toBytes(),
+        voterKeypair.publicKey.toBytes(),
+      ],
+      program.programId
+    )
+
+    const signature = await program.methods
+      .voteOnDispute({
+        vote: 1,
+      })
+      .accounts({
+        voter: voterKeypair.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda3,
+      })
+      .signers([voterKeypair])
+      .rpc({ commitment: 'confirmed' })
+
+    const quest = await program.account.quest.fetch(questPda3)
+    expect(quest.offereeVotes.toNumber()).to.be.equal(500 * 10 ** 9)
+
+    const [event] = await cpiEvents(program, signature)
+    expect(event.name).to.be.equal('voteCast')
+    expect(event.data.questId.equals(quest.id)).to.be.true
+    expect(event.data.actor.equals(voterKeypair.publicKey)).to.be.true
+    expect(event.data.vote).to.be.equal(1)
+    expect(event.data.power.toNumber()).to.be.equal(500 * 10 ** 9)
+
+    const questVote = await program.account.questVote.fetch(questVotePda)
+    expect(questVote.vote).to.be.equal(1)
+    expect(questVote.power.toNumber()).to.be.equal(500 * 10 ** 9)
+  })
+
+  it('Cannot vote on a dispute as a party of the quest', async () => {
+    try {
+      await program.methods
+        .voteOnDispute({
+          vote: 1,
+        })
+        .accounts({
+          voter: offereeKeypair.publicKey,
+        })
+        .accountsPartial({
+          quest: questPda3,
+        })
+        .signers([offereeKeypair])
+        .rpc()
+      expect.fail('the offeree should not be able to vote')
+    } catch (e) {
+      expect(e.error.errorCode.code).to.be.equal('PartyCannotVote')
+    }
+  })
+
+  it('Can resolve a dispute after the dispute duration', async () => {
+    let quest = await program.account.quest.fetch(questPda3)
+    config = await program.account.config.fetch(configPda)
+
+    await waitForSlot(
+      program,
+      quest.disputeTimestamp.toNumber() + config.disputeDuration.toNumber()
+    )
+
+    await program.methods
+      .resolveDispute()
+      .accountsPartial({
+        quest: questPda3,
+        owner: authority.publicKey,
+        offeree: offereeKeypair.publicKey,
+      })
+      .rpc()
+
+    quest = await program.account.quest.fetch(questPda3)
+    expect(quest.status).to.be.equal(14) // offeree won
+
+    const counter = await program.account.counter.fetch(counterPda)
+    expect(counter.postsInDispute.toNumber()).to.be.equal(0)
+    expect(counter.postsResolved.toNumber()).to.be.equal(1)
+  })
+
+  it('Can settle a resolved dispute', async () => {
+    // the offeree won, whoever settles pays for their badge
+    const [badgePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('badge'), questPda3.toBytes()],
+      program.programId
+    )
+    const [badgeMintPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('badge_mint'), questPda3.toBytes()],
+      program.programId
+    )
+    const offereeBadgeTokenAccount = await getAssociatedTokenAddress(
+      badgeMintPda,
+      offereeKeypair.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    )
+
+    await program.methods
+      .settleDispute()
+      .accounts({
+        offeree: offereeKeypair.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda3,
+        owner: authority.publicKey,
+        badge: badgePda,
+        badgeMint: badgeMintPda,
+        offereeBadgeTokenAccount,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        payer: authority.publicKey,
+      })
+      .rpc()
+
+    const badge = await program.account.badge.fetch(badgePda)
+    expect(badge.recipient.equals(offereeKeypair.publicKey)).to.be.true
+
+    const quest = await program.account.quest.fetchNullable(questPda3)
+    expect(quest).to.be.equal(null)
+
+    // the offeree won, taking both stakes
+    const offereeTokenAccount = await getAccount(
+      program.provider.connection,
+      offereeAta
+    )
+    expect(Number(offereeTokenAccount.amount) / 10 ** 9).to.be.equal(1100)
+
+    const ownerTokenAccount = await getAccount(
+      program.provider.connection,
+      ownerAta
+    )
+    expect(Number(ownerTokenAccount.amount) / 10 ** 9).to.be.equal(900)
+  })
+
+  it('Can unstake and withdraw vote power', async () => {
+    await program.methods
+      .unstakeVotePower()
+      .accounts({
+        owner: voterKeypair.publicKey,
+      })
+      .signers([voterKeypair])
+      .rpc()
+
+    let votePower = await program.account.votePower.fetch(votePowerPda)
+    expect(votePower.staked.toNumber()).to.be.equal(0)
+    expect(votePower.unstaked.toNumber()).to.be.equal(500 * 10 ** 9)
+    expect(votePower.unstakeTimestamp).to.not.be.null
+
+    // unstakedVoteUnlockInterval is 0 in the test config, releasing everything at once
+    await program.methods
+      .withdrawStakedVotes()
+      .accounts({
+        owner: voterKeypair.publicKey,
+      })
+      .signers([voterKeypair])
+      .rpc()
+
+    votePower = await program.account.votePower.fetch(votePowerPda)
+    expect(votePower.unstaked.toNumber()).to.be.equal(0)
+    expect(votePower.unstakeTimestamp).to.be.null
+
+    const tokenAccountInfo = await getAccount(
+      program.provider.connection,
+      voterAta
+    )
+    expect(Number(tokenAccountInfo.amount) / 10 ** 9).to.be.equal(1000)
+  })
+
+  it('Charges the placement decay fee when closing', async () => {
+    const preTreasuryBalance = await program.provider.connection.getBalance(
+      treasuryKeypair.publicKey
+    )
+
+    await program.methods
+      .createQuest(questParams({ placementPaid: new BN(0.01 * LAMPORTS_PER_SOL) }))
+      .accounts({
+        owner: authority.publicKey,
+        id: questId4.publicKey,
+      })
+      .signers([questId4])
+      .rpc()
+
+    await program.methods
+      .closeQuest()
+      .accounts({
+        owner: authority.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda4,
+      })
+      .rpc()
+
+    // decayStart is 0 in the test config, closing within a day charges at most a day of decay fee,
+    // none in the very slot of the creation (see the unit tests of Quest::decay_fee)
+    const postTreasuryBalance = await program.provider.connection.getBalance(
+      treasuryKeypair.publicKey
+    )
+    expect(postTreasuryBalance - preTreasuryBalance).to.be.oneOf([
+      config.baseFee.toNumber(),
+      config.baseFee.toNumber() + config.decayFee.toNumber(),
+    ])
+  })
+
+  it('Can update the config', async () => {
+    await program.methods
+      .updateConfig({
+        treasury: null,
+        baseFee: new BN(0.0002 * LAMPORTS_PER_SOL),
+        decayFee: null,
+        decayStart: null,
+        voteThreshold: null,
+        disputeDuration: new BN(40),
+        stakedVotePowerStart: null,
+        unstakedVoteUnlockInterval: null,
+        deliverableReviewPeriod: null,
+        rewardMint: null,
+        abandonPenalty: null,
+        abandonPenaltyToTreasury: null,
+        reviewPeriod: null,
+      })
+      .accounts({
+        authority: authority.publicKey,
+      })
+      .rpc()
+
+    const updated = await program.account.config.fetch(configPda)
+    expect(updated.baseFee.toNumber()).to.be.equal(0.0002 * LAMPORTS_PER_SOL)
+    expect(updated.disputeDuration.toNumber()).to.be.equal(40)
+    expect(updated.decayFee.eq(config.decayFee)).to.be.true
+    expect(updated.treasury.equals(config.treasury)).to.be.true
+
+    config = updated
+  })
+
+  it('Can set and clear the reward mint', async () => {
+    const updateRewardMint = (rewardMint: PublicKey) =>
+      program.methods
+        .updateConfig({
+          treasury: null,
+          baseFee: null,
+          decayFee: null,
+          decayStart: null,
+          voteThreshold: null,
+          disputeDuration: null,
+          stakedVotePowerStart: null,
+          unstakedVoteUnlockInterval: null,
+          deliverableReviewPeriod: null,
+          rewardMint,
+          abandonPenalty: null,
+          abandonPenaltyToTreasury: null,
+          reviewPeriod: null,
+        })
+        .accounts({
+          authority: authority.publicKey,
+        })
+        .rpc()
+
+    const rewardMint = Keypair.generate().publicKey
+    await updateRewardMint(rewardMint)
+    expect((await program.account.config.fetch(configPda)).rewardMint.equals(rewardMint)).to.be.true
+
+    // the default public key clears it
+    await updateRewardMint(PublicKey.default)
+    expect((await program.account.config.fetch(configPda)).rewardMint).to.be.null
+  })
+
+  it('Cannot update the config with invalid values', async () => {
+    try {
+      await program.methods
+        .updateConfig({
+          treasury: null,
+          baseFee: null,
+          decayFee: null,
+          decayStart: null,
+          voteThreshold: null,
+          disputeDuration: new BN(0),
+          stakedVotePowerStart: null,
+          unstakedVoteUnlockInterval: null,
+          deliverableReviewPeriod: null,
+          rewardMint: null,
+          abandonPenalty: null,
+          abandonPenaltyToTreasury: null,
+          reviewPeriod: null,
+        })
+        .accounts({
+          authority: authority.publicKey,
+        })
+        .rpc()
+      expect.fail('a zero dispute duration should be rejected')
+    } catch (e) {
+      expect(e.error.errorCode.code).to.be.equal('InvalidDisputeDuration')
+    }
+  })
+
+  it('Cannot update the config without the authority', async () => {
+    try {
+      await program.methods
+        .updateConfig({
+          treasury: offereeKeypair.publicKey,
+          baseFee: null,
+          decayFee: null,
+          decayStart: null,
+          voteThreshold: null,
+          disputeDuration: null,
+          stakedVotePowerStart: null,
+          unstakedVoteUnlockInterval: null,
+          deliverableReviewPeriod: null,
+          rewardMint: null,
+          abandonPenalty: null,
+          abandonPenaltyToTreasury: null,
+          reviewPeriod: null,
+        })
+        .accounts({
+          authority: offereeKeypair.publicKey,
+        })
+        .signers([offereeKeypair])
+        .rpc()
+      expect.fail('only the config authority can update the config')
+    } catch (e) {
+      expect(e.error.errorCode.code).to.be.equal('InvalidUpdateAuthority')
+    }
+  })
+
+  it('Can hand over the config authority in two steps', async () => {
+    await program.methods
+      .proposeAuthority({
+        newAuthority: offereeKeypair.publicKey,
+      })
+      .accounts({
+        authority: authority.publicKey,
+      })
+      .rpc()
+
+    // only the proposed authority can complete the handover
+    try {
+      await program.methods
+        .acceptAuthority()
+        .accounts({
+          pendingAuthority: voterKeypair.publicKey,
+        })
+        .signers([voterKeypair])
+        .rpc()
+      expect.fail('only the pending authority can accept')
+    } catch (e) {
+      expect(e.error.errorCode.code).to.be.equal('InvalidPendingAuthority')
+    }
+
+    await program.methods
+      .acceptAuthority()
+      .accounts({
+        pendingAuthority: offereeKeypair.publicKey,
+      })
+      .signers([offereeKeypair])
+      .rpc()
+
+    let updated = await program.account.config.fetch(configPda)
+    expect(updated.authority.equals(offereeKeypair.publicKey)).to.be.true
+    expect(updated.pendingAuthority).to.be.null
+
+    // hand it back for the rest of the tests
+    await program.methods
+      .proposeAuthority({
+        newAuthority: authority.publicKey,
+      })
+      .accounts({
+        authority: offereeKeypair.publicKey,
+      })
+      .signers([offereeKeypair])
+      .rpc()
+
+    await program.methods
+      .acceptAuthority()
+      .accounts({
+        pendingAuthority: authority.publicKey,
+      })
+      .rpc()
+
+    updated = await program.account.config.fetch(configPda)
+    expect(updated.authority.equals(authority.publicKey)).to.be.true
+  })
+
+  it('Can pause the quest instructions', async () => {
+    const questId = Keypair.generate()
+
+    // PAUSE_QUESTS
+    await program.methods
+      .setPause({
+        paused: 1,
+      })
+      .accounts({
+        authority: authority.publicKey,
+      })
+      .rpc()
+
+    try {
+      await program.methods
+        .createQuest(questParams())
+        .accounts({
+          owner: authority.publicKey,
+          id: questId.publicKey,
+        })
+        .signers([questId])
+        .rpc()
+      expect.fail('creating a quest should be paused')
+    } catch (e) {
+      expect(e.error.errorCode.code).to.be.equal('ProgramPaused')
+    }
+
+    const updated = await program.account.config.fetch(configPda)
+    expect(updated.paused).to.be.equal(1)
+
+    await program.methods
+      .setPause({
+        paused: 0,
+      })
+      .accounts({
+        authority: authority.publicKey,
+      })
+      .rpc()
+  })
+
+  it('Can expire an open quest past its acceptance deadline', async () => {
+    const questId = Keypair.generate()
+    const [questPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('quest'), questId.publicKey.toBytes()],
+      program.programId
+    )
+    const slot = await program.provider.connection.getSlot()
+
+    await program.methods
+      .createQuest(questParams({ acceptDeadline: new BN(slot + 5) }))
+      .accounts({
+        owner: authority.publicKey,
+        id: questId.publicKey,
+      })
+      .signers([questId])
+      .rpc()
+
+    await program.methods
+      .publishQuest()
+      .accounts({
+        owner: authority.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+      })
+      .rpc()
+
+    const preTokenAccount = await getAccount(
+      program.provider.connection,
+      ownerAta
+    )
+
+    await waitForSlot(program, slot + 5)
+
+    // the owner does not need to sign, anyone can crank the expiry
+    await program.methods
+      .expireQuest()
+      .accountsPartial({
+        quest: questPda,
+        owner: authority.publicKey,
+      })
+      .rpc()
+
+    // unpublished with the stake refunded, the owner can still close it
+    const quest = await program.account.quest.fetch(questPda)
+    expect(quest.status).to.be.equal(0)
+    expect(quest.staked.toNumber()).to.be.equal(0)
+
+    const postTokenAccount = await getAccount(
+      program.provider.connection,
+      ownerAta
+    )
+    expect(
+      Number(postTokenAccount.amount - preTokenAccount.amount) / 10 ** 9
+    ).to.be.equal(100)
+  })
+
+  it('Can republish and accept a quest expired past its delivery deadline', async () => {
+    const questId = Keypair.generate()
+    const [questPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('quest'), questId.publicKey.toBytes()],
+      program.programId
+    )
+    const slot = await program.provider.connection.getSlot()
+
+    await program.methods
+      .createQuest(
+        questParams({
+          acceptDeadline: new BN(slot + 20),
+          deliveryDeadline: new BN(slot + 25),
+        })
+      )
+      .accounts({
+        owner: authority.publicKey,
+        id: questId.publicKey,
+      })
+      .signers([questId])
+      .rpc()
+
+    await program.methods
+      .publishQuest()
+      .accounts({
+        owner: authority.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+      })
+      .rpc()
+
+    const acceptQuest = () =>
+      program.methods
+        .acceptQuest({
+          stakeAmount: new BN(100 * 10 ** 9),
+          offereeProposalHash: Array.from(
+            Keypair.generate().publicKey.toBytes()
+          ),
+        })
+        .accounts({
+          offeree: offereeKeypair.publicKey,
+        })
+        .accountsPartial({
+          quest: questPda,
+        })
+        .signers([offereeKeypair])
+        .rpc()
+
+    await acceptQuest()
+    await waitForSlot(program, slot + 25)
+
+    await program.methods
+      .expireQuest()
+      .accountsPartial({
+        quest: questPda,
+        owner: authority.publicKey,
+      })
+      .rpc()
+
+    let quest = await program.account.quest.fetch(questPda)
+    expect(quest.status).to.be.equal(0)
+    expect(quest.acceptDeadline).to.be.null
+    expect(quest.deliveryDeadline).to.be.null
+
+    await program.methods
+      .publishQuest()
+      .accounts({
+        owner: authority.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+      })
+      .rpc()
+
+    await acceptQuest()
+
+    quest = await program.account.quest.fetch(questPda)
+    expect(quest.status).to.be.equal(3)
+    expect(quest.offeree.equals(offereeKeypair.publicKey)).to.be.true
+  })
+
+  it('Can claim completion after the review period of a deliverable', async () => {
+    const questPda = await createTakenQuest()
+
+    const signature = await program.methods
+      .submitDeliverable({
+        deliverableHash: Array.from(Keypair.generate().publicKey.toBytes()),
+      })
+      .accounts({
+        offeree: offereeKeypair.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+      })
+      .signers([offereeKeypair])
+      .rpc({ commitment: 'confirmed' })
+
+    let quest = await program.account.quest.fetch(questPda)
+    expect(quest.deliverableHash).to.not.be.null
+
+    // the owner is notified that the review period started
+    const [event] = await cpiEvents(program, signature)
+    expect(event.name).to.be.equal('deliverableSubmitted')
+    expect(event.data.questId.equals(quest.id)).to.be.true
+    expect(event.data.deliverableHash).to.be.deep.equal(quest.deliverableHash)
+    expect(event.data.reviewEnds.toNumber()).to.be.equal(
+      quest.deliverableTimestamp.toNumber() +
+        config.deliverableReviewPeriod.toNumber()
+    )
+
+    // the owner stays silent during the review period
+    await waitForSlot(
+      program,
+      quest.deliverableTimestamp.toNumber() +
+        config.deliverableReviewPeriod.toNumber()
+    )
+
+    await program.methods
+      .claimCompletion()
+      .accounts({
+        offeree: offereeKeypair.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+        owner: authority.publicKey,
+      })
+      .signers([offereeKeypair])
+      .rpc()
+
+    quest = await program.account.quest.fetch(questPda)
+    expect(quest.status).to.be.equal(7)
+  })
+
+  it('Can apply to a quest and get approved by the owner', async () => {
+    const questId = Keypair.generate()
+    const [questPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('quest'), questId.publicKey.toBytes()],
+      program.programId
+    )
+
+    await program.methods
+      .createQuest(questParams({ minStakeRequired: new BN(50 * 10 ** 9) }))
+      .accounts({
+        owner: authority.publicKey,
+        id: questId.publicKey,
+      })
+      .signers([questId])
+      .rpc()
+
+    await program.methods
+      .publishQuest()
+      .accounts({
+        owner: authority.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+      })
+      .rpc()
+
+    // two competing applications
+    for (const applicant of [offereeKeypair, voterKeypair]) {
+      await program.methods
+        .applyToQuest({
+          stakeAmount: new BN(50 * 10 ** 9),
+          offereeProposalHash: Array.from(
+            Keypair.generate().publicKey.toBytes()
+          ),
+        })
+        .accounts({
+          offeree: applicant.publicKey,
+        })
+        .accountsPartial({
+          quest: questPda,
+        })
+        .signers([applicant])
+        .rpc()
+    }
+
+    // dust sent to the application's token account cannot block the approval
+    const [winnerApplicationPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('application'),
+        questPda.toBytes(),
+        offereeKeypair.publicKey.toBytes(),
+      ],
+      program.programId
+    )
+    await mintTo(
+      program.provider.connection,
+      authority,
+      tokenMint,
+      await getAssociatedTokenAddress(tokenMint, winnerApplicationPda, true),
+      authority,
+      1
+    )
+    const preOffereeTokenAccount = await getAccount(
+      program.provider.connection,
+      offereeAta
+    )
+
+    // the owner approves without the offeree's signature
+    await program.methods
+      .approveApplication()
+      .accounts({
+        offeree: offereeKeypair.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+      })
+      .rpc()
+
+    const quest = await program.account.quest.fetch(questPda)
+    expect(quest.status).to.be.equal(3)
+    expect(quest.offeree.equals(offereeKeypair.publicKey)).to.be.true
+    expect(quest.offereeStaked.toNumber()).to.be.equal(50 * 10 ** 9)
+
+    const postOffereeTokenAccount = await getAccount(
+      program.provider.connection,
+      offereeAta
+    )
+    expect(
+      Number(postOffereeTokenAccount.amount - preOffereeTokenAccount.amount)
+    ).to.be.equal(1)
+
+    // the losing applicant withdraws their stake
+    const [loserApplicationPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('application'),
+        questPda.toBytes(),
+        voterKeypair.publicKey.toBytes(),
+      ],
+      program.programId
+    )
+    const preTokenAccount = await getAccount(
+      program.provider.connection,
+      voterAta
+    )
+
+    await program.methods
+      .withdrawApplication()
+      .accounts({
+        offeree: voterKeypair.publicKey,
+      })
+      .accountsPartial({
+        application: loserApplicationPda,
+      })
+      .signers([voterKeypair])
+      .rpc()
+
+    const postTokenAccount = await getAccount(
+      program.provider.connection,
+      voterAta
+    )
+    expect(
+      Number(postTokenAccount.amount - preTokenAccount.amount) / 10 ** 9
+    ).to.be.equal(50)
+    expect(
+      await program.account.application.fetchNullable(loserApplicationPda)
+    ).to.be.null
+  })
+
+  it('Can accept a quest with an offer signed off-chain by the owner', async () => {
+    const questId = Keypair.generate()
+    const [questPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('quest'), questId.publicKey.toBytes()],
+      program.programId
+    )
+
+    await program.methods
+      .createQuest(questParams({ minStakeRequired: new BN(50 * 10 ** 9) }))
+      .accounts({
+        owner: authority.publicKey,
+        id: questId.publicKey,
+      })
+      .signers([questId])
+      .rpc()
+
+    await program.methods
+      .publishQuest()
+      .accounts({
+        owner: authority.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+      })
+      .rpc()
+
+    const proposalHash = Keypair.generate().publicKey.toBytes()
+    const minStake = new BN(60 * 10 ** 9)
+    const maxStake = new BN(80 * 10 ** 9)
+    const expirySlot = new BN((await program.provider.connection.getSlot()) + 100)
+
+    // signed by the owner, off-chain
+    const message = Buffer.concat([
+      Buffer.from('quest_board:offer'),
+      questId.publicKey.toBuffer(),
+      offereeKeypair.publicKey.toBuffer(),
+      new BN(0).toArrayLike(Buffer, 'le', 8), // offer nonce, no acceptance yet
+      minStake.toArrayLike(Buffer, 'le', 8),
+      maxStake.toArrayLike(Buffer, 'le', 8),
+      Buffer.from(proposalHash),
+      expirySlot.toArrayLike(Buffer, 'le', 8),
+    ])
+    const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: authority.secretKey,
+      message,
+    })
+
+    // submitted by the offeree alone
+    const acceptSignedOffer = () =>
+      program.methods
+        .acceptSignedOffer({
+          accept: {
+            stakeAmount: new BN(70 * 10 ** 9),
+            offereeProposalHash: Array.from(proposalHash),
+          },
+          minStake,
+          maxStake,
+          expirySlot,
+        })
+        .accounts({
+          offeree: offereeKeypair.publicKey,
+        })
+        .accountsPartial({
+          quest: questPda,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .preInstructions([ed25519Ix])
+        .signers([offereeKeypair])
+        .rpc()
+
+    await acceptSignedOffer()
+
+    const quest = await program.account.quest.fetch(questPda)
+    expect(quest.status).to.be.equal(3)
+    expect(quest.offeree.equals(offereeKeypair.publicKey)).to.be.true
+    expect(quest.offereeStaked.toNumber()).to.be.equal(70 * 10 ** 9)
+    expect(quest.offerNonce.toNumber()).to.be.equal(1)
+
+    // once the quest is open again, the same offer cannot be replayed
+    await program.methods
+      .abandonQuest()
+      .accounts({
+        offeree: offereeKeypair.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+        owner: authority.publicKey,
+      })
+      .signers([offereeKeypair])
+      .rpc()
+
+    let replayed = true
+    try {
+      await acceptSignedOffer()
+    } catch (e) {
+      replayed = false
+      expect(e.error.errorCode.code).to.be.equal('InvalidOfferSignature')
+    }
+    expect(replayed).to.be.false
+  })
+
+  it('Pays the SOL reward to the offeree on completion', async () => {
+    const reward = new BN(LAMPORTS_PER_SOL)
+    const questPda = await createTakenQuest(reward)
+
+    const quest = await program.account.quest.fetch(questPda)
+    expect(quest.reward.eq(reward)).to.be.true
+    expect(quest.rewardMint).to.be.null
+
+    const balanceBefore = await program.provider.connection.getBalance(offereeKeypair.publicKey)
+
+    await program.methods
+      .completeQuest()
+      .accounts({
+        offeree: offereeKeypair.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+      })
+      .signers([offereeKeypair])
+      .rpc()
+
+    const balanceAfter = await program.provider.connection.getBalance(offereeKeypair.publicKey)
+    expect(balanceAfter - balanceBefore).to.be.equal(LAMPORTS_PER_SOL)
+  })
+
+  it('Releases the current milestone of the reward', async () => {
+    const questPda = await createTakenQuest(new BN(LAMPORTS_PER_SOL), [
+      {
+        reward: new BN(0.4 * LAMPORTS_PER_SOL),
+        detailsHash: Array.from(Keypair.generate().publicKey.toBytes()),
+      },
+      {
+        reward: new BN(0.6 * LAMPORTS_PER_SOL),
+        detailsHash: Array.from(Keypair.generate().publicKey.toBytes()),
+      },
+    ])
+
+    const balanceBefore = await program.provider.connection.getBalance(offereeKeypair.publicKey)
+
+    await program.methods
+      .completeMilestone()
+      .accounts({
+        offeree: offereeKeypair.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+      })
+      .signers([offereeKeypair])
+      .rpc()
+
+    const balanceAfter = await program.provider.connection.getBalance(offereeKeypair.publicKey)
+    expect(balanceAfter - balanceBefore).to.be.equal(0.4 * LAMPORTS_PER_SOL)
+
+    const quest = await program.account.quest.fetch(questPda)
+    expect(quest.currentMilestone).to.be.equal(1)
+    expect(quest.milestones[0].completed).to.be.true
+    expect(quest.milestones[1].completed).to.be.false
+  })
+
+  it('Can cancel a taken quest with both signatures', async () => {
+    const questPda = await createTakenQuest()
+
+    const offereeTokenAccount = await getAssociatedTokenAddress(tokenMint, offereeKeypair.publicKey)
+    const balanceBefore = (await getAccount(program.provider.connection, offereeTokenAccount)).amount
+
+    await program.methods
+      .cancelQuest()
+      .accounts({
+        offeree: offereeKeypair.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+      })
+      .signers([offereeKeypair])
+      .rpc()
+
+    const balanceAfter = (await getAccount(program.provider.connection, offereeTokenAccount)).amount
+    expect(Number(balanceAfter - balanceBefore) / 10 ** 9).to.be.equal(100)
+
+    const quest = await program.account.quest.fetch(questPda)
+    expect(quest.status).to.be.equal(0)
+    expect(quest.offeree).to.be.null
+    expect(quest.offereeStaked).to.be.null
+  })
+
+  it('Reopens an abandoned quest, forfeiting the penalty to the owner', async () => {
+    const questPda = await createTakenQuest()
+
+    const ownerTokenAccount = await getAssociatedTokenAddress(tokenMint, authority.publicKey)
+    const offereeTokenAccount = await getAssociatedTokenAddress(tokenMint, offereeKeypair.publicKey)
+    const ownerBefore = (await getAccount(program.provider.connection, ownerTokenAccount)).amount
+    const offereeBefore = (await getAccount(program.provider.connection, offereeTokenAccount)).amount
+
+    await program.methods
+      .abandonQuest()
+      .accounts({
+        offeree: offereeKeypair.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+        owner: authority.publicKey,
+      })
+      .signers([offereeKeypair])
+      .rpc()
+
+    // 10% of the 100 staked
+    const ownerAfter = (await getAccount(program.provider.connection, ownerTokenAccount)).amount
+    const offereeAfter = (await getAccount(program.provider.connection, offereeTokenAccount)).amount
+    expect(Number(ownerAfter - ownerBefore) / 10 ** 9).to.be.equal(10)
+    expect(Number(offereeAfter - offereeBefore) / 10 ** 9).to.be.equal(90)
+
+    const quest = await program.account.quest.fetch(questPda)
+    expect(quest.status).to.be.equal(1)
+    expect(quest.offeree).to.be.null
+    expect(quest.acceptedTimestamp).to.be.null
+  })
+
+  it('Tracks reputation and enforces the minimum required by the quest', async () => {
+    const [offereeProfilePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('profile'), offereeKeypair.publicKey.toBytes()],
+      program.programId
+    )
+
+    // completed the quest of the SOL reward and the milestones, won a dispute, abandoned two quests
+    const profile = await program.account.userProfile.fetch(offereeProfilePda)
+    expect(profile.questsTaken.toNumber()).to.be.greaterThan(0)
+    expect(profile.disputesWon.toNumber()).to.be.equal(1)
+    expect(profile.questsAbandoned.toNumber()).to.be.equal(2)
+
+    const questId = Keypair.generate()
+    const [questPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('quest'), questId.publicKey.toBytes()],
+      program.programId
+    )
+
+    await program.methods
+      .createQuest(questParams({ minReputation: new BN(1000) }))
+      .accounts({
+        owner: authority.publicKey,
+        id: questId.publicKey,
+      })
+      .signers([questId])
+      .rpc()
+
+    await program.methods
+      .publishQuest()
+      .accounts({
+        owner: authority.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+      })
+      .rpc()
+
+    try {
+      await program.methods
+        .acceptQuest({
+          stakeAmount: new BN(100 * 10 ** 9),
+          offereeProposalHash: Array.from(Keypair.generate().publicKey.toBytes()),
+        })
+        .accounts({
+          offeree: offereeKeypair.publicKey,
+        })
+        .accountsPartial({
+          quest: questPda,
+        })
+        .signers([offereeKeypair])
+        .rpc()
+      expect.fail('the offeree reputation is below the minimum')
+    } catch (e) {
+      expect(e.error.errorCode.code).to.be.equal('ReputationTooLow')
+    }
+  })
+
+  it('Forfeits the abandon penalty to the treasury when configured', async () => {
+    const setPenaltyToTreasury = (abandonPenaltyToTreasury: boolean) =>
+      program.methods
+        .updateConfig({
+          treasury: null,
+          baseFee: null,
+          decayFee: null,
+          decayStart: null,
+          voteThreshold: null,
+          disputeDuration: null,
+          stakedVotePowerStart: null,
+          unstakedVoteUnlockInterval: null,
+          deliverableReviewPeriod: null,
+          rewardMint: null,
+          abandonPenalty: null,
+          abandonPenaltyToTreasury,
+          reviewPeriod: null,
+        })
+        .accounts({
+          authority: authority.publicKey,
+        })
+        .rpc()
+
+    const questPda = await createTakenQuest()
+    const treasuryTokenAccount = await getOrCreateAssociatedTokenAccount(
+      program.provider.connection,
+      authority,
+      tokenMint,
+      treasuryKeypair.publicKey
+    )
+
+    await setPenaltyToTreasury(true)
+
+    await program.methods
+      .abandonQuest()
+      .accounts({
+        offeree: offereeKeypair.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+        owner: authority.publicKey,
+        treasuryTokenAccount: treasuryTokenAccount.address,
+      })
+      .signers([offereeKeypair])
+      .rpc()
+
+    await setPenaltyToTreasury(false)
+
+    // 10% of the 100 staked
+    const treasuryAfter = (await getAccount(program.provider.connection, treasuryTokenAccount.address)).amount
+    expect(Number(treasuryAfter - treasuryTokenAccount.amount) / 10 ** 9).to.be.equal(10)
+  })
+
+  it('Mints a non-transferable badge to the offeree on completion', async () => {
+    const questPda = await createTakenQuest()
+
+    const [badgePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('badge'), questPda.toBytes()],
+      program.programId
+    )
+    const [badgeMintPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('badge_mint'), questPda.toBytes()],
+      program.programId
+    )
+    const offereeBadgeTokenAccount = await getAssociatedTokenAddress(
+      badgeMintPda,
+      offereeKeypair.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    )
+
+    await program.methods
+      .completeQuest()
+      .accounts({
+        offeree: offereeKeypair.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+        badge: badgePda,
+        badgeMint: badgeMintPda,
+        offereeBadgeTokenAccount,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([offereeKeypair])
+      .rpc()
+
+    const badge = await program.account.badge.fetch(badgePda)
+    const quest = await program.account.quest.fetch(questPda)
+    expect(badge.recipient.equals(offereeKeypair.publicKey)).to.be.true
+    expect(badge.mint.equals(badgeMintPda)).to.be.true
+    expect(badge.questId.equals(quest.id)).to.be.true
+
+    const tokenAccountInfo = await getAccount(
+      program.provider.connection,
+      offereeBadgeTokenAccount,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    )
+    expect(Number(tokenAccountInfo.amount)).to.be.equal(1)
+  })
+
+  it('Can review the other party of a complete quest once', async () => {
+    const questPda = await createTakenQuest()
+
+    await program.methods
+      .completeQuest()
+      .accounts({
+        offeree: offereeKeypair.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+      })
+      .signers([offereeKeypair])
+      .rpc()
+
+    const [ownerProfilePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('profile'), authority.publicKey.toBytes()],
+      program.programId
+    )
+    const profileBefore = await program.account.userProfile.fetch(ownerProfilePda)
+
+    const review = () =>
+      program.methods
+        .leaveReview({
+          rating: 5,
+          reviewHash: Array.from(Keypair.generate().publicKey.toBytes()),
+        })
+        .accounts({
+          reviewer: offereeKeypair.publicKey,
+        })
+        .accountsPartial({
+          quest: questPda,
+          revieweeProfile: ownerProfilePda,
+        })
+        .signers([offereeKeypair])
+        .rpc()
+
+    await review()
+
+    const profileAfter = await program.account.userProfile.fetch(ownerProfilePda)
+    expect(profileAfter.ratingCount.toNumber()).to.be.equal(profileBefore.ratingCount.toNumber() + 1)
+    expect(profileAfter.ratingSum.toNumber()).to.be.equal(profileBefore.ratingSum.toNumber() + 5)
+
+    // the review PDA already exists
+    let reviewedTwice = true
+    try {
+      await review()
+    } catch {
+      reviewedTwice = false
+    }
+    expect(reviewedTwice).to.be.false
+  })
+
+  it('Emits the status transition as a CPI event', async () => {
+    const questId = Keypair.generate()
+    const [questPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('quest'), questId.publicKey.toBytes()],
+      program.programId
+    )
+
+    await program.methods
+      .createQuest(questParams())
+      .accounts({
+        owner: authority.publicKey,
+        id: questId.publicKey,
+      })
+      .signers([questId])
+      .rpc()
+
+    const signature = await program.methods
+      .publishQuest()
+      .accounts({
+        owner: authority.publicKey,
+      })
+      .accountsPartial({
+        quest: questPda,
+      })
+      .rpc({ commitment: 'confirmed' })
+
+    const tx = await program.provider.connection.getTransaction(signature, {
+      commitment: 'confirmed',
+      maxSupportedTransactionVersion: 0,
+    })
+
+    // the event is the data of the self-invoked inner instruction, after the 8 bytes event tag
+    const eventIx = tx.meta.innerInstructions[0].instructions[0]
+    const eventData = utils.bytes.base64.encode(
+      Buffer.from(utils.bytes.bs58.decode(eventIx.data)).subarray(8)
+    )
+    const event = program.coder.events.decode(eventData)
+
+    expect(event.name).to.be.equal('questStatusChanged')
+    expect(event.data.questId.equals(questId.publicKey)).to.be.true
+    expect(event.data.actor.equals(authority.publicKey)).to.be.true
+    expect(event.data.oldStatus).to.be.equal(0)
+    expect(event.data.newStatus).to.be.equal(1)
+  })
+
+  it('Creates quests with the current layout version, which need no migration', async () => {
+    const questId = Keypair.generate()
+    const [questPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('quest'), questId.publicKey.toBytes()],
+      program.programId
+    )
+
+    await program.methods
+      .createQuest(questParams())
+      .accounts({
+        owner: authority.publicKey,
+        id: questId.publicKey,
+      })
+      .signers([questId])
+      .rpc()
+
+    const quest = await program.account.quest.fetch(questPda)
+    expect(quest.version).to.be.equal(1)
+
+    let migrated = true
+    try {
+      await program.methods
+        .migrateQuest()
+        .accounts({
+          quest: questPda,
+          owner: authority.publicKey,
+        })
+        .rpc()
+    } catch (e) {
+      migrated = false
+      expect(e.error.errorCode.code).to.be.equal('AlreadyMigrated')
+    }
+    expect(migrated).to.be.false
+  })
+
+  it('Grows a baseline quest account when its owner writes it', async () => {
+    // see [[test.validator.account]] in Anchor.toml, an unpublished quest of 249 bytes
+    const legacyOwner = loadKeypair('tests/fixtures/legacy-quest-owner.json')
+    const legacyQuestPda = new PublicKey(
+      'Gv8FSBxyzP85uQ8FwRkh3rSttVEiBpzzNVPdcPWki5Vs'
+    )
+
+    await program.provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({
+          fromPubkey: authority.publicKey,
+          toPubkey: legacyOwner.publicKey,
+          lamports: LAMPORTS_PER_SOL,
+        })
+      )
+    )
+
+    let accountInfo = await program.provider.connection.getAccountInfo(
+      legacyQuestPda
+    )
+    expect(accountInfo.data.length).to.be.equal(249)
+
+    // PAUSE_QUESTS
+    await program.methods
+      .setPause({
+        paused: 1,
+      })
+      .accounts({
+        authority: authority.publicKey,
+      })
+      .rpc()
+
+    try {
+      await program.methods
+        .migrateQuest()
+        .accounts({
+          quest: legacyQuestPda,
+          owner: legacyOwner.publicKey,
+        })
+        .signers([legacyOwner])
+        .rpc()
+      expect.fail('migrating a quest should be paused')
+    } catch (e) {
+      expect(e.error.errorCode.code).to.be.equal('ProgramPaused')
+    }
+
+    await program.methods
+      .setPause({
+        paused: 0,
+      })
+      .accounts({
+        authority: authority.publicKey,
+      })
+      .rpc()
+
+    // anyone but the owner needs the quest migrated first
+    try {
+      await program.methods
+        .raiseDispute()
+        .accounts({
+          signer: authority.publicKey,
+        })
+        .accountsPartial({
+          quest: legacyQuestPda,
+          owner: legacyOwner.publicKey,
+          offeree: offereeKeypair.publicKey,
+        })
+        .rpc()
+      expect.fail('a legacy quest should need a migration')
+    } catch (e) {
+      expect(e.error.errorCode.code).to.be.equal('QuestNotMigrated')
+    }
+
+    // the owner needs no migration, publishing grows the account to the current layout
+    await program.methods
+      .publishQuest()
+      .accounts({
+        owner: legacyOwner.publicKey,
+      })
+      .accountsPartial({
+        quest: legacyQuestPda,
+      })
+      .signers([legacyOwner])
+      .rpc()
+
+    accountInfo = await program.provider.connection.getAccountInfo(
+      legacyQuestPda
+    )
+    expect(accountInfo.data.length).to.be.equal(653)
+
+    const quest = await program.account.quest.fetch(legacyQuestPda)
+    expect(quest.status).to.be.equal(1)
+    expect(quest.owner.equals(legacyOwner.publicKey)).to.be.true
+    expect(quest.version).to.be.equal(1)
+    expect(quest.milestones).to.be.empty
+    expect(quest.rewardMint).to.be.null
+
+    await program.methods
+      .unpublishQuest()
+      .accounts({
+        owner: legacyOwner.publicKey,
+      })
+      .accountsPartial({
+        quest: legacyQuestPda,
+      })
+      .signers([legacyOwner])
+      .rpc()
+  })
 })